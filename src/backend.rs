@@ -0,0 +1,210 @@
+//! Traits for code generic over the backing type of a [`NanoBV`].
+//!
+//! The inherent methods of [`NanoBV`] are generated once per backing type, so
+//! they cannot be called on a `NanoBV<T>` with an unknown `T`. [`BitVector`]
+//! exposes them as trait methods instead:
+//!
+//! ```
+//! use nanobv::{backend::BitVector, NanoBV};
+//!
+//! fn parity<B: BitVector>(bv: B) -> bool {
+//!     bv.count_ones() % 2 == 1
+//! }
+//!
+//! assert!(parity(NanoBV::<u8>::new(0b111, 3)));
+//! assert!(!parity(NanoBV::<u64>::ones(64)));
+//! ```
+
+use crate::{Error, NanoBV};
+use core::{fmt::Debug, hash::Hash};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Unsigned integer type that can back a [`NanoBV`].
+///
+/// This trait is sealed and implemented for `u8`, `u16`, `u32` and `u64`.
+pub trait BvStorage:
+    sealed::Sealed + Copy + Eq + Ord + Hash + Debug + Default
+{
+    /// Size of the type in bits.
+    const BIT_SIZE: usize;
+
+    /// Zero-extend the value to a `u64`.
+    fn to_u64(self) -> u64;
+
+    /// Truncate a `u64` to the type.
+    fn from_u64(value: u64) -> Self;
+}
+
+/// Bitvector operations shared by every [`NanoBV`] backing type.
+///
+/// Each method forwards to the inherent method of the same name.
+pub trait BitVector: Copy + Eq + Debug {
+    /// Backing type of the bitvector.
+    type Storage: BvStorage;
+
+    /// Create a new bitvector.
+    fn new(data: Self::Storage, length: usize) -> Self;
+    /// Create a new bitvector, rejecting invalid lengths.
+    fn try_new(data: Self::Storage, length: usize) -> Result<Self, Error>;
+    /// Create a bitvector with all bits unset.
+    fn zeros(length: usize) -> Self;
+    /// Create a bitvector with all bits set.
+    fn ones(length: usize) -> Self;
+    /// Retrieve length of the bitvector.
+    fn len(&self) -> usize;
+    /// Always `false`, as the length of a bitvector is never zero.
+    #[deprecated(
+        since = "0.1.2",
+        note = "the length of a NanoBV is never zero"
+    )]
+    fn is_empty(&self) -> bool {
+        false
+    }
+    /// Retrieve value of the bitvector.
+    fn value(&self) -> Self::Storage;
+    /// Retrieve value of the bitvector interpreted as two's complement.
+    fn signed_value(&self) -> i64;
+    /// Set value of the bitvector while retaining length.
+    fn set_value(&self, value: Self::Storage) -> Self;
+    /// Count set bits.
+    fn count_ones(&self) -> usize;
+    /// Count unset bits within the declared length.
+    fn count_zeros(&self) -> usize;
+    /// Get bit at offset.
+    fn get_bit(&self, offset: Self::Storage) -> Self::Storage;
+    /// Set bit at offset.
+    fn set_bit(&self, offset: Self::Storage) -> Self;
+    /// Clear bit at offset.
+    fn clear_bit(&self, offset: Self::Storage) -> Self;
+    /// Assign bit at offset.
+    fn assign_bit(&self, value: Self::Storage, offset: Self::Storage) -> Self;
+    /// Reverse bits.
+    fn reverse(&self) -> Self;
+    /// Add, keeping the shorter length.
+    fn bvadd(&self, rhs: Self) -> Self;
+    /// Bitwise AND, keeping the shorter length.
+    fn bvand(&self, rhs: Self) -> Self;
+    /// Bitwise OR, keeping the shorter length.
+    fn bvor(&self, rhs: Self) -> Self;
+    /// Bitwise XOR, keeping the shorter length.
+    fn bvxor(&self, rhs: Self) -> Self;
+    /// Subtract, keeping the shorter length.
+    fn bvsub(&self, rhs: Self) -> Self;
+    /// Shift left, keeping the shorter length.
+    fn bvshl(&self, rhs: Self) -> Self;
+    /// Shift right, keeping the shorter length.
+    fn bvshr(&self, rhs: Self) -> Self;
+}
+
+macro_rules! ImplBackend {
+    (for $($type:tt),+) => {
+        $(ImplBackend!($type);)*
+    };
+
+    ($type:ident) => {
+        impl sealed::Sealed for $type {}
+
+        impl BvStorage for $type {
+            const BIT_SIZE: usize = NanoBV::<$type>::BIT_SIZE;
+
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            fn from_u64(value: u64) -> Self {
+                value as $type
+            }
+        }
+
+        impl NanoBV<$type> {
+            /// Compare values after zero-extending both to the wider backing type,
+            /// ignoring lengths.
+            pub fn eq_zext<U: BvStorage>(&self, rhs: NanoBV<U>) -> bool {
+                self.data as u64 == rhs.data.to_u64()
+            }
+        }
+
+        impl BitVector for NanoBV<$type> {
+            type Storage = $type;
+
+            fn new(data: $type, length: usize) -> Self { NanoBV::<$type>::new(data, length) }
+            fn try_new(data: $type, length: usize) -> Result<Self, Error> { NanoBV::<$type>::try_new(data, length) }
+            fn zeros(length: usize) -> Self { NanoBV::<$type>::zeros(length) }
+            fn ones(length: usize) -> Self { NanoBV::<$type>::ones(length) }
+            fn len(&self) -> usize { NanoBV::<$type>::len(self) }
+            fn value(&self) -> $type { NanoBV::<$type>::value(self) }
+            fn signed_value(&self) -> i64 { NanoBV::<$type>::signed_value(self) }
+            fn set_value(&self, value: $type) -> Self { NanoBV::<$type>::set_value(self, value) }
+            fn count_ones(&self) -> usize { NanoBV::<$type>::count_ones(self) }
+            fn count_zeros(&self) -> usize { NanoBV::<$type>::count_zeros(self) }
+            fn get_bit(&self, offset: $type) -> $type { NanoBV::<$type>::get_bit(self, offset) }
+            fn set_bit(&self, offset: $type) -> Self { NanoBV::<$type>::set_bit(self, offset) }
+            fn clear_bit(&self, offset: $type) -> Self { NanoBV::<$type>::clear_bit(self, offset) }
+            fn assign_bit(&self, value: $type, offset: $type) -> Self { NanoBV::<$type>::assign_bit(self, value, offset) }
+            fn reverse(&self) -> Self { NanoBV::<$type>::reverse(self) }
+            fn bvadd(&self, rhs: Self) -> Self { NanoBV::<$type>::bvadd(self, rhs) }
+            fn bvand(&self, rhs: Self) -> Self { NanoBV::<$type>::bvand(self, rhs) }
+            fn bvor(&self, rhs: Self) -> Self { NanoBV::<$type>::bvor(self, rhs) }
+            fn bvxor(&self, rhs: Self) -> Self { NanoBV::<$type>::bvxor(self, rhs) }
+            fn bvsub(&self, rhs: Self) -> Self { NanoBV::<$type>::bvsub(self, rhs) }
+            fn bvshl(&self, rhs: Self) -> Self { NanoBV::<$type>::bvshl(self, rhs) }
+            fn bvshr(&self, rhs: Self) -> Self { NanoBV::<$type>::bvshr(self, rhs) }
+        }
+    };
+}
+
+ImplBackend!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    fn widen<B: BitVector>(bv: B) -> u64 {
+        bv.value().to_u64()
+    }
+
+    fn rotate_left<B: BitVector>(bv: B) -> B {
+        let msb = bv.value().to_u64() >> (bv.len() - 1);
+        let shifted = (bv.value().to_u64() << 1) | msb;
+        bv.set_value(B::Storage::from_u64(shifted))
+    }
+
+    macro_rules! ImplBackendTest {
+        (for $($type:tt),+) => {
+            $(ImplBackendTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_backend_forwarding_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                assert_eq!(<$type as BvStorage>::BIT_SIZE, NBV::BIT_SIZE);
+                assert_eq!(widen(bv), bv.value() as u64);
+                assert_eq!(BitVector::reverse(&bv), bv.reverse());
+                assert_eq!(BitVector::count_zeros(&bv), bv.count_zeros());
+                assert_eq!(<NBV as BitVector>::ones(4), NBV::ones(4));
+                assert_eq!(rotate_left(NBV::new(0b1001, 4)), NBV::new(0b0011, 4));
+                assert_eq!(<$type>::from_u64(u64::MAX), $type::MAX);
+            }
+
+            #[test]
+            fn [<test_nanobv_eq_zext_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert!(NBV::new(0xA5, 8).eq_zext(NanoBV::<u64>::new(0xA5, 64)));
+                assert!(NBV::new(0x5, 4).eq_zext(NanoBV::<u8>::new(0x5, 8)));
+                assert!(!NBV::new(0xA5, 8).eq_zext(NanoBV::<u16>::new(0x1A5, 16)));
+            }
+        }
+        };
+    }
+
+    ImplBackendTest!(for u8, u16, u32, u64);
+}
@@ -0,0 +1,152 @@
+//! Packed binary-coded decimal conversions and arithmetic.
+//!
+//! Each nibble holds one decimal digit, least significant digit first. The
+//! operations return `None` for nibbles above 9 and for lengths that are not
+//! a whole number of nibbles.
+
+use crate::NanoBV;
+
+macro_rules! ImplNanoBVBcd {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVBcd!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            const fn bcd_is_valid(&self) -> bool {
+                let mut i = 0;
+                while i < self.len() {
+                    if (self.data >> i) & 0xF > 9 {
+                        return false;
+                    }
+                    i += 4;
+                }
+                self.len() % 4 == 0
+            }
+
+            /// Convert the value to packed BCD, with the length rounded up to a
+            /// whole number of nibbles.
+            ///
+            /// Returns `None` if the value has more digits than fit in that length.
+            pub const fn to_bcd(&self) -> Option<Self> {
+                let length = self.len().div_ceil(4) * 4;
+                let (mut value, mut bcd, mut i) = (self.data, 0 as $type, 0);
+                while value != 0 {
+                    if i >= length {
+                        return None;
+                    }
+                    bcd |= (value % 10) << i;
+                    value /= 10;
+                    i += 4;
+                }
+                Some(NanoBV::<$type>::new(bcd, length))
+            }
+
+            /// Convert packed BCD to binary, retaining length.
+            ///
+            /// Returns `None` if the value does not fit in the length.
+            pub const fn from_bcd(&self) -> Option<Self> {
+                if !self.bcd_is_valid() {
+                    return None;
+                }
+                let (mut value, mut i) = (0u64, self.len());
+                while i > 0 {
+                    i -= 4;
+                    value = value * 10 + ((self.data >> i) & 0xF) as u64;
+                }
+                match value > Self::upper_bound(self.length) as u64 {
+                true => None,
+                false => Some(NanoBV::<$type>::new(value as $type, self.len())),
+                }
+            }
+
+            /// Add packed BCD `rhs` and `carry`, returning the sum and the decimal
+            /// carry out of the shorter length.
+            pub const fn bcd_add(&self, rhs: Self, carry: bool) -> Option<(Self, bool)> {
+                if !self.bcd_is_valid() || !rhs.bcd_is_valid() {
+                    return None;
+                }
+                let length = $crate::internals::min(self.len(), rhs.len());
+                let (mut sum, mut carry, mut i) = (0 as $type, carry as $type, 0);
+                while i < length {
+                    let mut digit = ((self.data >> i) & 0xF) + ((rhs.data >> i) & 0xF) + carry;
+                    carry = (digit > 9) as $type;
+                    if digit > 9 {
+                        digit -= 10;
+                    }
+                    sum |= digit << i;
+                    i += 4;
+                }
+                Some((NanoBV::<$type>::new(sum, length), carry == 1))
+            }
+
+            /// Subtract packed BCD `rhs` and `borrow`, returning the difference and
+            /// the decimal borrow out of the shorter length.
+            pub const fn bcd_sub(&self, rhs: Self, borrow: bool) -> Option<(Self, bool)> {
+                if !self.bcd_is_valid() || !rhs.bcd_is_valid() {
+                    return None;
+                }
+                let length = $crate::internals::min(self.len(), rhs.len());
+                let (mut difference, mut borrow, mut i) = (0 as $type, borrow as $type, 0);
+                while i < length {
+                    let (lhs, rhs) = ((self.data >> i) & 0xF, ((rhs.data >> i) & 0xF) + borrow);
+                    let digit = match lhs < rhs {
+                    true => lhs + 10 - rhs,
+                    false => lhs - rhs,
+                    };
+                    borrow = (lhs < rhs) as $type;
+                    difference |= digit << i;
+                    i += 4;
+                }
+                Some((NanoBV::<$type>::new(difference, length), borrow == 1))
+            }
+        }
+    };
+}
+
+ImplNanoBVBcd!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{WyRand, RNG};
+
+    macro_rules! ImplNanoBVBcdTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVBcdTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_to_from_bcd_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(59, 8).to_bcd(), Some(NBV::new(0x59, 8)));
+                assert_eq!(NBV::new(7, 3).to_bcd(), Some(NBV::new(0x7, 4)));
+                assert_eq!(NBV::new(123, 8).to_bcd(), None);
+                assert_eq!(NBV::new(0x59, 8).from_bcd(), Some(NBV::new(59, 8)));
+                assert_eq!(NBV::new(0x5A, 8).from_bcd(), None);
+                assert_eq!(NBV::new(0x5, 6).from_bcd(), None);
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate_range(0, 100), 8);
+                assert_eq!(bv.to_bcd().and_then(|bcd| bcd.from_bcd()), Some(bv));
+            }
+
+            #[test]
+            fn [<test_nanobv_bcd_add_sub_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0x38, 8).bcd_add(NBV::new(0x45, 8), false), Some((NBV::new(0x83, 8), false)));
+                assert_eq!(NBV::new(0x99, 8).bcd_add(NBV::new(0x01, 8), false), Some((NBV::new(0x00, 8), true)));
+                assert_eq!(NBV::new(0x9, 4).bcd_add(NBV::new(0x9, 4), true), Some((NBV::new(0x9, 4), true)));
+                assert_eq!(NBV::new(0x83, 8).bcd_sub(NBV::new(0x45, 8), false), Some((NBV::new(0x38, 8), false)));
+                assert_eq!(NBV::new(0x00, 8).bcd_sub(NBV::new(0x01, 8), false), Some((NBV::new(0x99, 8), true)));
+                assert_eq!(NBV::new(0x10, 8).bcd_sub(NBV::new(0x09, 8), true), Some((NBV::new(0x00, 8), false)));
+                assert_eq!(NBV::new(0x1A, 8).bcd_add(NBV::new(0x01, 8), false), None);
+            }
+        }
+        };
+    }
+
+    ImplNanoBVBcdTest!(for u8, u16, u32, u64);
+}
@@ -0,0 +1,147 @@
+//! 8×8 bitboards.
+//!
+//! A bitboard is a 64-bit `NanoBV<u64>` with square `8 * rank + file` at bit
+//! `8 * rank + file`, so bit 0 is a1, bit 7 is h1 and bit 63 is h8. North is
+//! towards rank 8 and east is towards file h.
+
+use crate::{NanoBV, SetBits};
+
+/// Squares of the a-file.
+pub const FILE_A: NanoBV<u64> = NanoBV::<u64>::new(0x0101_0101_0101_0101, 64);
+/// Squares of the h-file.
+pub const FILE_H: NanoBV<u64> = NanoBV::<u64>::new(0x8080_8080_8080_8080, 64);
+/// Squares of the first rank.
+pub const RANK_1: NanoBV<u64> = NanoBV::<u64>::new(0x0000_0000_0000_00FF, 64);
+/// Squares of the eighth rank.
+pub const RANK_8: NanoBV<u64> = NanoBV::<u64>::new(0xFF00_0000_0000_0000, 64);
+
+impl NanoBV<u64> {
+    /// Create a bitboard with the single square at `file` and `rank` set.
+    pub const fn square(file: u64, rank: u64) -> Self {
+        assert!(file < 8 && rank < 8, "Invalid square provided.");
+        NanoBV::<u64>::one_hot(8 * rank + file, 64)
+    }
+
+    /// Shift every square one rank north, dropping the eighth rank.
+    pub const fn north(&self) -> Self {
+        assert!(self.len() == 64, "Invalid length provided.");
+        NanoBV::<u64>::new(self.data << 8, 64)
+    }
+
+    /// Shift every square one rank south, dropping the first rank.
+    pub const fn south(&self) -> Self {
+        assert!(self.len() == 64, "Invalid length provided.");
+        NanoBV::<u64>::new(self.data >> 8, 64)
+    }
+
+    /// Shift every square one file east, dropping the h-file.
+    pub const fn east(&self) -> Self {
+        assert!(self.len() == 64, "Invalid length provided.");
+        NanoBV::<u64>::new((self.data & !FILE_H.data) << 1, 64)
+    }
+
+    /// Shift every square one file west, dropping the a-file.
+    pub const fn west(&self) -> Self {
+        assert!(self.len() == 64, "Invalid length provided.");
+        NanoBV::<u64>::new((self.data & !FILE_A.data) >> 1, 64)
+    }
+
+    /// Shift every square one step north-east.
+    pub const fn north_east(&self) -> Self {
+        self.east().north()
+    }
+
+    /// Shift every square one step north-west.
+    pub const fn north_west(&self) -> Self {
+        self.west().north()
+    }
+
+    /// Shift every square one step south-east.
+    pub const fn south_east(&self) -> Self {
+        self.east().south()
+    }
+
+    /// Shift every square one step south-west.
+    pub const fn south_west(&self) -> Self {
+        self.west().south()
+    }
+
+    /// Flip the bitboard vertically, swapping the first and eighth ranks.
+    pub const fn flip_vertical(&self) -> Self {
+        assert!(self.len() == 64, "Invalid length provided.");
+        NanoBV::<u64>::new(self.data.swap_bytes(), 64)
+    }
+
+    /// Mirror the bitboard horizontally, swapping the a-file and h-file.
+    pub const fn mirror_horizontal(&self) -> Self {
+        assert!(self.len() == 64, "Invalid length provided.");
+        let mut x = self.data;
+        x = ((x >> 1) & 0x5555_5555_5555_5555)
+            | ((x & 0x5555_5555_5555_5555) << 1);
+        x = ((x >> 2) & 0x3333_3333_3333_3333)
+            | ((x & 0x3333_3333_3333_3333) << 2);
+        x = ((x >> 4) & 0x0F0F_0F0F_0F0F_0F0F)
+            | ((x & 0x0F0F_0F0F_0F0F_0F0F) << 4);
+        NanoBV::<u64>::new(x, 64)
+    }
+
+    /// Iterate over the occupied squares, from a1 to h8.
+    pub const fn occupied(&self) -> SetBits<u64> {
+        self.iter_set_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    #[test]
+    fn test_bitboard_shifts() {
+        let a1 = NanoBV::<u64>::square(0, 0);
+        let h8 = NanoBV::<u64>::square(7, 7);
+        assert_eq!(a1.north(), NanoBV::<u64>::square(0, 1));
+        assert_eq!(a1.east(), NanoBV::<u64>::square(1, 0));
+        assert_eq!(a1.north_east(), NanoBV::<u64>::square(1, 1));
+        assert_eq!(h8.south_west(), NanoBV::<u64>::square(6, 6));
+        assert_eq!(a1.west(), NanoBV::<u64>::zeros(64));
+        assert_eq!(a1.south(), NanoBV::<u64>::zeros(64));
+        assert_eq!(h8.east(), NanoBV::<u64>::zeros(64));
+        assert_eq!(h8.north_west(), NanoBV::<u64>::zeros(64));
+        assert_eq!(FILE_H.east(), NanoBV::<u64>::zeros(64));
+        assert_eq!(FILE_A.west(), NanoBV::<u64>::zeros(64));
+        assert_eq!(FILE_A.east().east().west().west(), FILE_A);
+        assert_eq!(RANK_8.north(), NanoBV::<u64>::zeros(64));
+        assert_eq!(RANK_1.north_west(), NanoBV::<u64>::new(0x7F00, 64));
+        assert_eq!(RANK_1.south_east(), NanoBV::<u64>::zeros(64));
+    }
+
+    #[test]
+    fn test_bitboard_flip_mirror() {
+        assert_eq!(RANK_1.flip_vertical(), RANK_8);
+        assert_eq!(FILE_A.mirror_horizontal(), FILE_H);
+        assert_eq!(
+            NanoBV::<u64>::square(2, 1).flip_vertical(),
+            NanoBV::<u64>::square(2, 6)
+        );
+        assert_eq!(
+            NanoBV::<u64>::square(2, 1).mirror_horizontal(),
+            NanoBV::<u64>::square(5, 1)
+        );
+        let mut rng = RNG::<WyRand, u64>::new(u64::MAX as _);
+        let board = NanoBV::<u64>::new(rng.generate(), 64);
+        assert_eq!(board.flip_vertical().flip_vertical(), board);
+        assert_eq!(board.mirror_horizontal().mirror_horizontal(), board);
+        assert_eq!(board.flip_vertical().mirror_horizontal(), board.reverse());
+    }
+
+    #[test]
+    fn test_bitboard_occupied() {
+        let board = NanoBV::<u64>::square(0, 0) | NanoBV::<u64>::square(4, 3);
+        let mut squares = board.occupied();
+        assert_eq!(squares.next(), Some(0));
+        assert_eq!(squares.next(), Some(28));
+        assert_eq!(squares.next(), None);
+        assert_eq!(RANK_8.occupied().count(), 8);
+    }
+}
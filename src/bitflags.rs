@@ -0,0 +1,136 @@
+//! Conversions between bitflags-style flag types and NanoBVs.
+//!
+//! Flag types declare their backing type and width through [`FlagBits`],
+//! which [`impl_flag_bits!`](crate::impl_flag_bits) implements for any type
+//! with inherent `bits(&self)` and `from_bits(bits) -> Option<Self>` methods,
+//! such as those generated by the `bitflags` crate.
+//!
+//! ```
+//! use nanobv::{impl_flag_bits, NanoBV};
+//!
+//! #[derive(Copy, Clone, PartialEq, Debug)]
+//! struct Status(u8);
+//!
+//! impl Status {
+//!     const READY: Status = Status(0b001);
+//!     const ERROR: Status = Status(0b100);
+//!
+//!     const fn bits(&self) -> u8 {
+//!         self.0
+//!     }
+//!
+//!     const fn from_bits(bits: u8) -> Option<Self> {
+//!         match bits & !0b111 {
+//!             0 => Some(Status(bits)),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! impl_flag_bits!(Status: u8, 3);
+//!
+//! let bv = NanoBV::<u8>::from_flags(Status(Status::READY.0 | Status::ERROR.0)).unwrap();
+//! assert_eq!(bv, NanoBV::<u8>::new(0b101, 3));
+//! assert_eq!(bv.count_ones(), 2);
+//! assert_eq!(bv.clear_bit(2).to_flags(), Some(Status::READY));
+//! ```
+
+use crate::{backend::BvStorage, NanoBV};
+
+/// Flag type convertible to and from a [`NanoBV`] of a declared width.
+pub trait FlagBits: Copy {
+    /// Backing type of the flags.
+    type Bits: BvStorage;
+
+    /// Number of bits used by the flags.
+    const WIDTH: usize;
+
+    /// Retrieve the raw bits of the flags.
+    fn to_bits(self) -> Self::Bits;
+
+    /// Create flags from raw bits, or `None` if a bit has no flag.
+    fn from_bits_checked(bits: Self::Bits) -> Option<Self>;
+}
+
+/// Implement [`FlagBits`](crate::bitflags::FlagBits) for a flag type with
+/// inherent `bits` and `from_bits` methods, given its backing type and width.
+#[macro_export]
+macro_rules! impl_flag_bits {
+    ($flags:ty : $bits:ty, $width:expr) => {
+        impl $crate::bitflags::FlagBits for $flags {
+            type Bits = $bits;
+            const WIDTH: usize = $width;
+
+            fn to_bits(self) -> $bits {
+                <$flags>::bits(&self)
+            }
+
+            fn from_bits_checked(bits: $bits) -> Option<Self> {
+                <$flags>::from_bits(bits)
+            }
+        }
+    };
+}
+
+macro_rules! ImplNanoBVFlagBits {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVFlagBits!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Create a NanoBV of the declared width of `F` holding the bits of
+            /// `flags`, or `None` if a set bit lies beyond that width.
+            pub fn from_flags<F: FlagBits<Bits = $type>>(flags: F) -> Option<Self> {
+                let bits = flags.to_bits();
+                match F::WIDTH < Self::BIT_SIZE && bits >> F::WIDTH != 0 {
+                true => None,
+                false => Some(NanoBV::<$type>::new(bits, F::WIDTH)),
+                }
+            }
+
+            /// Convert the current NanoBV to flags, or `None` if a set bit has no
+            /// flag.
+            pub fn to_flags<F: FlagBits<Bits = $type>>(&self) -> Option<F> {
+                F::from_bits_checked(self.data)
+            }
+        }
+    };
+}
+
+ImplNanoBVFlagBits!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct Control(u16);
+
+    impl Control {
+        const fn bits(&self) -> u16 {
+            self.0
+        }
+
+        const fn from_bits(bits: u16) -> Option<Self> {
+            match bits >> 10 {
+                0 => Some(Control(bits)),
+                _ => None,
+            }
+        }
+    }
+
+    impl_flag_bits!(Control: u16, 10);
+
+    #[test]
+    fn test_nanobv_flags() {
+        let bv = NanoBV::<u16>::from_flags(Control(0b10_0000_0011)).unwrap();
+        assert_eq!(bv, NanoBV::<u16>::new(0b10_0000_0011, 10));
+        assert_eq!(bv.to_flags(), Some(Control(0b10_0000_0011)));
+        assert_eq!(bv.clear_bit(9).to_flags(), Some(Control(0b11)));
+        assert_eq!(NanoBV::<u16>::new(1 << 12, 16).to_flags::<Control>(), None);
+        assert_eq!(<Control as FlagBits>::WIDTH, 10);
+        assert_eq!(NanoBV::<u16>::from_flags(Control(1 << 11)), None);
+        assert_eq!(NanoBV::<u16>::from_flags(Control(1 << 15)), None);
+    }
+}
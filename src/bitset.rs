@@ -0,0 +1,181 @@
+//! Set semantics over NanoBVs.
+//!
+//! ```
+//! use nanobv::bitset::BitSet;
+//!
+//! let mut primes = BitSet::<u16>::new(16);
+//! for p in [2, 3, 5, 7, 11, 13] {
+//!     primes.insert(p);
+//! }
+//! let mut odd = BitSet::<u16>::new(16);
+//! for n in (1..16).step_by(2) {
+//!     odd.insert(n);
+//! }
+//!
+//! assert!(primes.contains(13));
+//! assert_eq!(primes.difference(odd).iter().collect::<Vec<_>>(), [2]);
+//! ```
+
+use crate::{NanoBV, SetBits};
+
+/// Set of the members `0..capacity`, with member `n` stored in bit `n`.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct BitSet<T = u32> {
+    bits: NanoBV<T>,
+}
+
+impl<T: Copy> BitSet<T> {
+    /// Retrieve the underlying NanoBV.
+    pub const fn bits(&self) -> NanoBV<T> {
+        self.bits
+    }
+
+    /// Retrieve the number of possible members.
+    pub const fn capacity(&self) -> usize {
+        self.bits.len()
+    }
+}
+
+macro_rules! ImplBitSet {
+    (for $($type:tt),+) => {
+        $(ImplBitSet!($type);)*
+    };
+
+    ($type:ident) => {
+        impl BitSet<$type> {
+            /// Create an empty [`BitSet`] holding members below `capacity`.
+            pub const fn new(capacity: usize) -> Self {
+                BitSet { bits: NanoBV::<$type>::zeros(capacity) }
+            }
+
+            /// Create a [`BitSet`] with the members set in `bits`.
+            pub const fn from_bits(bits: NanoBV<$type>) -> Self {
+                BitSet { bits }
+            }
+
+            /// Retrieve the number of members.
+            pub const fn len(&self) -> usize {
+                self.bits.count_ones()
+            }
+
+            /// Check whether the set has no members.
+            pub const fn is_empty(&self) -> bool {
+                self.bits.value() == 0
+            }
+
+            /// Check whether `member` is in the set.
+            pub const fn contains(&self, member: $type) -> bool {
+                (member as usize) < self.capacity() && self.bits.get_bit(member) == 1
+            }
+
+            /// Add `member` to the set, returning whether it was newly inserted.
+            pub fn insert(&mut self, member: $type) -> bool {
+                ["Invalid member provided."][((member as usize) >= self.capacity()) as usize];
+                let inserted = !self.contains(member);
+                self.bits.set_bit_mut(member);
+                inserted
+            }
+
+            /// Remove `member` from the set, returning whether it was present.
+            pub fn remove(&mut self, member: $type) -> bool {
+                let removed = self.contains(member);
+                if removed {
+                    self.bits.clear_bit_mut(member);
+                }
+                removed
+            }
+
+            /// Retrieve the members in either set.
+            pub const fn union(&self, rhs: Self) -> Self {
+                ["Invalid capacity provided."][(self.capacity() != rhs.capacity()) as usize];
+                BitSet { bits: self.bits.bvor(rhs.bits) }
+            }
+
+            /// Retrieve the members in both sets.
+            pub const fn intersection(&self, rhs: Self) -> Self {
+                ["Invalid capacity provided."][(self.capacity() != rhs.capacity()) as usize];
+                BitSet { bits: self.bits.bvand(rhs.bits) }
+            }
+
+            /// Retrieve the members in the current set but not in `rhs`.
+            pub const fn difference(&self, rhs: Self) -> Self {
+                ["Invalid capacity provided."][(self.capacity() != rhs.capacity()) as usize];
+                BitSet { bits: NanoBV::<$type>::new(self.bits.value() & !rhs.bits.value(), self.capacity()) }
+            }
+
+            /// Check whether every member of the current set is in `rhs`.
+            pub const fn is_subset(&self, rhs: Self) -> bool {
+                self.bits.value() & !rhs.bits.value() == 0
+            }
+
+            /// Iterate over the members in ascending order.
+            pub const fn iter(&self) -> SetBits<$type> {
+                self.bits.iter_set_bits()
+            }
+        }
+
+        impl IntoIterator for BitSet<$type> {
+            type Item = $type;
+            type IntoIter = SetBits<$type>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+    };
+}
+
+ImplBitSet!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplBitSetTest {
+        (for $($type:tt),+) => {
+            $(ImplBitSetTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_bitset_members_ $type>]() {
+                let mut set = BitSet::<$type>::new(8);
+                assert!(set.is_empty());
+                assert!(set.insert(3));
+                assert!(set.insert(7));
+                assert!(!set.insert(3));
+                assert!(set.contains(3));
+                assert!(!set.contains(4));
+                assert!(!set.contains(8));
+                assert_eq!(set.len(), 2);
+                assert_eq!(set.bits(), NanoBV::<$type>::new(0b1000_1000, 8));
+                let mut members = set.iter();
+                assert_eq!(members.next(), Some(3));
+                assert_eq!(members.next(), Some(7));
+                assert_eq!(members.next(), None);
+                assert!(set.remove(3));
+                assert!(!set.remove(3));
+                assert!(!set.remove(9));
+                assert_eq!(set.into_iter().count(), 1);
+            }
+
+            #[test]
+            fn [<test_bitset_algebra_ $type>]() {
+                let a = BitSet::<$type>::from_bits(NanoBV::<$type>::new(0b1100, 4));
+                let b = BitSet::<$type>::from_bits(NanoBV::<$type>::new(0b1010, 4));
+                assert_eq!(a.union(b).bits(), NanoBV::<$type>::new(0b1110, 4));
+                assert_eq!(a.intersection(b).bits(), NanoBV::<$type>::new(0b1000, 4));
+                assert_eq!(a.difference(b).bits(), NanoBV::<$type>::new(0b0100, 4));
+                assert!(a.intersection(b).is_subset(a));
+                assert!(a.is_subset(a.union(b)));
+                assert!(!a.is_subset(b));
+                assert!(BitSet::<$type>::new(4).is_subset(b));
+            }
+        }
+        };
+    }
+
+    ImplBitSetTest!(for u8, u16, u32, u64);
+}
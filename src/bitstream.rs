@@ -0,0 +1,301 @@
+//! Bit-granular writer and reader over byte buffers.
+//!
+//! Bits are packed most significant bit first within each byte, and the bits
+//! of a NanoBV are written from its most significant bit, so a stream reads
+//! left to right in a binary dump. Positive integers can also be stored as
+//! self-delimiting Elias gamma and delta codes:
+//!
+//! ```
+//! use nanobv::{
+//!     bitstream::{BitReader, BitWriter},
+//!     NanoBV,
+//! };
+//!
+//! let mut buf = [0; 2];
+//! let mut writer = BitWriter::new(&mut buf);
+//! writer.write(NanoBV::<u8>::new(0b101, 3)).unwrap();
+//! writer.write(NanoBV::<u16>::new(0x1FF, 9)).unwrap();
+//! assert_eq!(writer.finish(), 2);
+//! assert_eq!(buf, [0b1011_1111, 0b1111_0000]);
+//!
+//! let mut reader = BitReader::new(&buf);
+//! assert_eq!(reader.read::<NanoBV<u8>>(3), Ok(NanoBV::<u8>::new(0b101, 3)));
+//! assert_eq!(reader.read::<NanoBV<u16>>(9), Ok(NanoBV::<u16>::new(0x1FF, 9)));
+//!
+//! let mut buf = [0; 3];
+//! let mut writer = BitWriter::new(&mut buf);
+//! writer.write_gamma(300).unwrap();
+//! assert_eq!(BitReader::new(&buf).read_gamma(), Ok(300));
+//! ```
+
+use crate::{
+    backend::{BitVector, BvStorage},
+    Error, NanoBV,
+};
+
+/// Writer appending bits to a byte buffer.
+#[derive(PartialEq, Debug, Eq)]
+pub struct BitWriter<'a> {
+    buf: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> BitWriter<'a> {
+    /// Create a [`BitWriter`] starting at the first bit of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        BitWriter { buf, position: 0 }
+    }
+
+    /// Retrieve the number of bits written.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Write a single bit, or return [`Error::BufferTooSmall`] if the buffer is
+    /// full.
+    pub fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        let byte =
+            self.buf.get_mut(self.position / 8).ok_or(Error::BufferTooSmall)?;
+        let mask = 0x80 >> (self.position % 8);
+        match bit {
+            true => *byte |= mask,
+            false => *byte &= !mask,
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Write the bits of `bits`, most significant bit first. Nothing is
+    /// written if they do not all fit.
+    pub fn write<B: BitVector>(&mut self, bits: B) -> Result<(), Error> {
+        if self.position + bits.len() > self.buf.len() * 8 {
+            return Err(Error::BufferTooSmall);
+        }
+        for offset in (0..bits.len()).rev() {
+            self.write_bit((bits.value().to_u64() >> offset) & 1 == 1)?;
+        }
+        Ok(())
+    }
+
+    /// Write `value` as an Elias gamma code: the bit length of `value` minus
+    /// one as zeros, followed by `value` itself. Returns
+    /// [`Error::InvalidFormat`] for zero, which has no code. Nothing is written
+    /// if the code does not fit.
+    pub fn write_gamma(&mut self, value: u64) -> Result<(), Error> {
+        let length = gamma_length(value)?;
+        if self.position + 2 * length - 1 > self.buf.len() * 8 {
+            return Err(Error::BufferTooSmall);
+        }
+        for _ in 1..length {
+            self.write_bit(false)?;
+        }
+        self.write(NanoBV::<u64>::new(value, length))
+    }
+
+    /// Write `value` as an Elias delta code: the bit length of `value` as a
+    /// gamma code, followed by `value` without its leading one. Returns
+    /// [`Error::InvalidFormat`] for zero, which has no code. Nothing is written
+    /// if the code does not fit.
+    pub fn write_delta(&mut self, value: u64) -> Result<(), Error> {
+        let length = gamma_length(value)?;
+        let prefix = 2 * gamma_length(length as u64)? - 1;
+        if self.position + prefix + length - 1 > self.buf.len() * 8 {
+            return Err(Error::BufferTooSmall);
+        }
+        self.write_gamma(length as u64)?;
+        match length {
+            1 => Ok(()),
+            _ => self.write(NanoBV::<u64>::new(value, length - 1)),
+        }
+    }
+
+    /// Retrieve the number of bytes holding the bits written, with unused
+    /// bits of the last byte left as zero.
+    pub fn finish(self) -> usize {
+        let used = self.position.div_ceil(8);
+        let unused = used * 8 - self.position;
+        if unused != 0 {
+            self.buf[used - 1] &= 0xFF << unused;
+        }
+        used
+    }
+}
+
+/// Reader consuming bits from a byte slice.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Create a [`BitReader`] starting at the first bit of `bytes`.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, position: 0 }
+    }
+
+    /// Retrieve the number of bits read.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Retrieve the number of bits left.
+    pub const fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+
+    /// Read a single bit, or return [`Error::InvalidFormat`] past the end of
+    /// the input.
+    pub fn read_bit(&mut self) -> Result<bool, Error> {
+        let byte =
+            self.bytes.get(self.position / 8).ok_or(Error::InvalidFormat)?;
+        let bit = (byte << (self.position % 8)) & 0x80 != 0;
+        self.position += 1;
+        Ok(bit)
+    }
+
+    /// Read `length` bits into a bitvector, most significant bit first.
+    /// Nothing is consumed on error.
+    pub fn read<B: BitVector>(&mut self, length: usize) -> Result<B, Error> {
+        if length < 1 || length > B::Storage::BIT_SIZE {
+            return Err(Error::InvalidLength);
+        }
+        if length > self.remaining() {
+            return Err(Error::InvalidFormat);
+        }
+        let mut value = 0u64;
+        for _ in 0..length {
+            value = (value << 1) | self.read_bit()? as u64;
+        }
+        Ok(B::new(B::Storage::from_u64(value), length))
+    }
+
+    /// Read an Elias gamma code, as written by [`BitWriter::write_gamma`].
+    /// Nothing is consumed on error.
+    pub fn read_gamma(&mut self) -> Result<u64, Error> {
+        let mut reader = *self;
+        let mut length = 1;
+        while !reader.read_bit()? {
+            length += 1;
+            if length > 64 {
+                return Err(Error::InvalidFormat);
+            }
+        }
+        let value = match length {
+            1 => 1,
+            _ => {
+                (1 << (length - 1))
+                    | reader.read::<NanoBV<u64>>(length - 1)?.value()
+            }
+        };
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Read an Elias delta code, as written by [`BitWriter::write_delta`].
+    /// Nothing is consumed on error.
+    pub fn read_delta(&mut self) -> Result<u64, Error> {
+        let mut reader = *self;
+        let value = match reader.read_gamma()? {
+            1 => 1,
+            length @ 2..=64 => {
+                (1 << (length - 1))
+                    | reader.read::<NanoBV<u64>>(length as usize - 1)?.value()
+            }
+            _ => return Err(Error::InvalidFormat),
+        };
+        *self = reader;
+        Ok(value)
+    }
+}
+
+/// Retrieve the bit length of a value with an Elias code.
+const fn gamma_length(value: u64) -> Result<usize, Error> {
+    match value {
+        0 => Err(Error::InvalidFormat),
+        value => Ok(64 - value.leading_zeros() as usize),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitstream_roundtrip() {
+        let mut buf = [0xFF; 4];
+        let mut writer = BitWriter::new(&mut buf);
+        writer.write_bit(false).unwrap();
+        writer.write(NanoBV::<u32>::new(0x12345, 20)).unwrap();
+        writer.write(NanoBV::<u8>::new(0b11, 2)).unwrap();
+        assert_eq!(writer.position(), 23);
+        assert_eq!(
+            writer.write(NanoBV::<u16>::ones(10)),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(writer.position(), 23);
+        assert_eq!(writer.finish(), 3);
+        assert_eq!(buf, [0x09, 0x1A, 0x2E, 0xFF]);
+        let mut reader = BitReader::new(&buf[..3]);
+        assert_eq!(reader.read_bit(), Ok(false));
+        assert_eq!(
+            reader.read::<NanoBV<u32>>(20),
+            Ok(NanoBV::<u32>::new(0x12345, 20))
+        );
+        assert_eq!(reader.read::<NanoBV<u8>>(9), Err(Error::InvalidLength));
+        assert_eq!(reader.read::<NanoBV<u8>>(4), Err(Error::InvalidFormat));
+        assert_eq!(reader.remaining(), 3);
+        assert_eq!(
+            reader.read::<NanoBV<u8>>(3),
+            Ok(NanoBV::<u8>::new(0b110, 3))
+        );
+        assert_eq!(reader.read_bit(), Err(Error::InvalidFormat));
+    }
+
+    #[test]
+    fn test_bitstream_elias() {
+        let mut buf = [0; 4];
+        let mut writer = BitWriter::new(&mut buf);
+        writer.write_gamma(1).unwrap();
+        writer.write_gamma(5).unwrap();
+        writer.write_delta(1).unwrap();
+        writer.write_delta(10).unwrap();
+        assert_eq!(writer.write_gamma(0), Err(Error::InvalidFormat));
+        assert_eq!(writer.write_delta(0), Err(Error::InvalidFormat));
+        assert_eq!(writer.position(), 15);
+        assert_eq!(writer.write_gamma(u64::MAX), Err(Error::BufferTooSmall));
+        assert_eq!(writer.write_delta(1 << 16), Err(Error::BufferTooSmall));
+        assert_eq!(writer.position(), 15);
+        assert_eq!(writer.finish(), 2);
+        // 1 | 00101 | 1 | 00100010
+        assert_eq!(buf[..2], [0b1001_0110, 0b0100_0100]);
+        let mut reader = BitReader::new(&buf[..2]);
+        assert_eq!(reader.read_gamma(), Ok(1));
+        assert_eq!(reader.read_gamma(), Ok(5));
+        assert_eq!(reader.read_delta(), Ok(1));
+        assert_eq!(reader.read_delta(), Ok(10));
+        assert_eq!(reader.read_gamma(), Err(Error::InvalidFormat));
+        assert_eq!(
+            BitReader::new(&[0x08, 0x80]).read_delta(),
+            Err(Error::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn test_bitstream_elias_limits() {
+        let mut buf = [0; 32];
+        let mut writer = BitWriter::new(&mut buf);
+        writer.write_gamma(u64::MAX).unwrap();
+        writer.write_delta(u64::MAX).unwrap();
+        writer.write_delta(1 << 40).unwrap();
+        let used = writer.finish();
+        let mut reader = BitReader::new(&buf[..used]);
+        assert_eq!(reader.read_gamma(), Ok(u64::MAX));
+        assert_eq!(reader.read_delta(), Ok(u64::MAX));
+        assert_eq!(reader.read_delta(), Ok(1 << 40));
+        assert_eq!(
+            BitReader::new(&[0; 9]).read_gamma(),
+            Err(Error::InvalidFormat)
+        );
+    }
+}
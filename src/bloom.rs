@@ -0,0 +1,159 @@
+//! Bloom filters over a single NanoBV.
+//!
+//! Keys are mixed with SplitMix64 and probed with double hashing, so probe
+//! `i` of a key sets bit `((h1 + i * h2) mod 2^32) * length >> 32`, a range
+//! reduction needing no division. Byte strings can be turned into keys with
+//! [`hash_bytes`].
+
+use crate::NanoBV;
+
+/// Mix `key` with the SplitMix64 finalizer.
+const fn mix(key: u64) -> u64 {
+    let mut z = key.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Hash a byte string into a key with 64-bit FNV-1a.
+pub const fn hash_bytes(bytes: &[u8]) -> u64 {
+    let (mut hash, mut i) = (0xCBF2_9CE4_8422_2325u64, 0);
+    while i < bytes.len() {
+        hash = (hash ^ bytes[i] as u64).wrapping_mul(0x0000_0100_0000_01B3);
+        i += 1;
+    }
+    hash
+}
+
+/// Bloom filter with `probes` hash probes into the bits of a [`NanoBV`].
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct BloomFilter<T = u64> {
+    bits: NanoBV<T>,
+    probes: usize,
+}
+
+impl<T: Copy> BloomFilter<T> {
+    /// Retrieve the underlying bit array.
+    pub const fn bits(&self) -> NanoBV<T> {
+        self.bits
+    }
+
+    /// Retrieve the number of hash probes per key.
+    pub const fn probes(&self) -> usize {
+        self.probes
+    }
+}
+
+macro_rules! ImplBloomFilter {
+    (for $($type:tt),+) => {
+        $(ImplBloomFilter!($type);)*
+    };
+
+    ($type:ident) => {
+        impl BloomFilter<$type> {
+            /// Create an empty [`BloomFilter`] of `length` bits with `probes`
+            /// hash probes per key.
+            pub const fn new(length: usize, probes: usize) -> Self {
+                ["Invalid probe count provided."][(probes == 0) as usize];
+                BloomFilter { bits: NanoBV::<$type>::zeros(length), probes }
+            }
+
+            /// Retrieve the bits probed for `key`.
+            const fn probe_mask(&self, key: u64) -> $type {
+                let hash = mix(key);
+                let (h1, h2) = (hash & 0xFFFF_FFFF, (hash >> 32) | 1);
+                let (mut mask, mut i) = (0 as $type, 0);
+                while i < self.probes {
+                    let probe = h1.wrapping_add(h2.wrapping_mul(i as u64)) as u32;
+                    mask |= 1 << ((probe as u64 * self.bits.len() as u64) >> 32);
+                    i += 1;
+                }
+                mask
+            }
+
+            /// Add `key` to the filter.
+            pub const fn insert(&self, key: u64) -> Self {
+                BloomFilter { bits: self.bits.set_value(self.bits.value() | self.probe_mask(key)), probes: self.probes }
+            }
+
+            /// Add `key` to the filter in place.
+            pub fn insert_mut(&mut self, key: u64) {
+                *self = self.insert(key);
+            }
+
+            /// Check whether `key` may be in the filter. False positives are
+            /// possible, false negatives are not.
+            pub const fn contains(&self, key: u64) -> bool {
+                let mask = self.probe_mask(key);
+                self.bits.value() & mask == mask
+            }
+
+            /// Merge the keys of `rhs` into the filter.
+            pub const fn union(&self, rhs: Self) -> Self {
+                ["Invalid filter provided."][(self.bits.len() != rhs.bits.len() || self.probes != rhs.probes) as usize];
+                BloomFilter { bits: self.bits.bvor(rhs.bits), probes: self.probes }
+            }
+        }
+    };
+}
+
+ImplBloomFilter!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplBloomFilterTest {
+        (for $($type:tt),+) => {
+            $(ImplBloomFilterTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_bloom_filter_ $type>]() {
+                let mut filter = BloomFilter::<$type>::new(NanoBV::<$type>::BIT_SIZE, 2);
+                assert!(!filter.contains(42));
+                filter.insert_mut(42);
+                filter = filter.insert(hash_bytes(b"uart0"));
+                assert!(filter.contains(42));
+                assert!(filter.contains(hash_bytes(b"uart0")));
+                assert!(filter.bits().count_ones() <= 4);
+                let other = BloomFilter::<$type>::new(NanoBV::<$type>::BIT_SIZE, 2).insert(7);
+                let merged = filter.union(other);
+                assert!(merged.contains(7) && merged.contains(42));
+                assert_eq!(merged.probes(), 2);
+            }
+
+            #[test]
+            fn [<test_bloom_filter_probe_range_ $type>]() {
+                for length in [3, 5, 7, NanoBV::<$type>::BIT_SIZE - 1] {
+                    let filter = BloomFilter::<$type>::new(length, 4);
+                    for key in 0..256 {
+                        assert_eq!(filter.probe_mask(key) >> length, 0);
+                    }
+                }
+            }
+        }
+        };
+    }
+
+    ImplBloomFilterTest!(for u8, u16, u32, u64);
+
+    #[test]
+    fn test_bloom_filter_false_positives() {
+        let mut filter = BloomFilter::<u64>::new(64, 3);
+        for key in 0..8 {
+            filter.insert_mut(key);
+        }
+        assert!((0..8).all(|key| filter.contains(key)));
+        assert!((1000..1100).filter(|&key| filter.contains(key)).count() < 25);
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(hash_bytes(b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(hash_bytes(b"a"), 0xAF63_DC4C_8601_EC8C);
+    }
+}
@@ -0,0 +1,214 @@
+//! Assembly of NanoBVs from variable-width fields.
+//!
+//! Fields are appended below the ones before them, so the first field ends up
+//! most significant, as in [`NanoBV::concat`](crate::NanoBV::concat) and in
+//! the usual notation of instruction encodings:
+//!
+//! ```
+//! use nanobv::{builder::Built, builder::NanoBVBuilder, bv_concat, NanoBV};
+//!
+//! // RISC-V `addi x1, x2, -1`.
+//! const ADDI: NanoBV<u32> = bv_concat!(u32; 0xFFF => 12, 2 => 5, 0b000 => 3, 1 => 5, 0b001_0011 => 7);
+//! assert_eq!(ADDI, NanoBV::<u32>::new(0xFFF1_0093, 32));
+//!
+//! let built = NanoBVBuilder::new().field(0b10, 2).bits(NanoBV::<u16>::new(0x3FF, 10)).finish();
+//! assert_eq!(built, Some(Built::U16(NanoBV::<u16>::new(0xBFF, 12))));
+//! ```
+//!
+//! With [`bv_concat!`](crate::bv_concat), a total width that does not fit the
+//! backing type is rejected at compile time:
+//!
+//! ```compile_fail
+//! use nanobv::bv_concat;
+//!
+//! let _ = bv_concat!(u8; 1 => 4, 2 => 5);
+//! ```
+
+use crate::{
+    backend::{BitVector, BvStorage},
+    Error, NanoBV,
+};
+
+/// Builder appending fields of given widths, up to 64 bits in total.
+#[derive(PartialEq, Debug, Eq, Copy, Clone, Default)]
+pub struct NanoBVBuilder {
+    data: u64,
+    length: usize,
+}
+
+/// NanoBV in the smallest backing type holding the fields of a
+/// [`NanoBVBuilder`].
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum Built {
+    /// Up to 8 bits.
+    U8(NanoBV<u8>),
+    /// 9 to 16 bits.
+    U16(NanoBV<u16>),
+    /// 17 to 32 bits.
+    U32(NanoBV<u32>),
+    /// 33 to 64 bits.
+    U64(NanoBV<u64>),
+}
+
+impl NanoBVBuilder {
+    /// Create an empty [`NanoBVBuilder`].
+    pub const fn new() -> Self {
+        NanoBVBuilder { data: 0, length: 0 }
+    }
+
+    /// Retrieve the total width of the fields appended so far.
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Check whether no field was appended yet.
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Retrieve the value of the fields appended so far.
+    pub const fn value(&self) -> u64 {
+        self.data
+    }
+
+    /// Append `value` as a field of `width` bits.
+    ///
+    /// Panics if `width` is zero, if `value` does not fit in `width` bits, or
+    /// if the total width exceeds 64 bits.
+    pub const fn field(self, value: u64, width: usize) -> Self {
+        assert!(
+            width >= 1 && self.length + width <= 64,
+            "Invalid width provided."
+        );
+        assert!(width == 64 || value >> width == 0, "Invalid value provided.");
+        let shifted = match width {
+            64 => 0,
+            _ => self.data << width,
+        };
+        NanoBVBuilder { data: shifted | value, length: self.length + width }
+    }
+
+    /// Append the bits of `bits` as a field of its length.
+    pub fn bits<B: BitVector>(self, bits: B) -> Self {
+        self.field(bits.value().to_u64(), bits.len())
+    }
+
+    /// Finish into the smallest backing type holding the fields, or `None` if
+    /// no field was appended.
+    pub const fn finish(self) -> Option<Built> {
+        match self.length {
+            0 => None,
+            1..=8 => {
+                Some(Built::U8(NanoBV::<u8>::new(self.data as u8, self.length)))
+            }
+            9..=16 => Some(Built::U16(NanoBV::<u16>::new(
+                self.data as u16,
+                self.length,
+            ))),
+            17..=32 => Some(Built::U32(NanoBV::<u32>::new(
+                self.data as u32,
+                self.length,
+            ))),
+            _ => Some(Built::U64(NanoBV::<u64>::new(self.data, self.length))),
+        }
+    }
+
+    /// Finish into a bitvector of the given backing type, returning
+    /// [`Error::InvalidLength`] if no field was appended or the fields do not
+    /// fit.
+    pub fn build<B: BitVector>(self) -> Result<B, Error> {
+        B::try_new(B::Storage::from_u64(self.data), self.length)
+    }
+}
+
+/// Concatenate fields written `value => width` into a
+/// [`NanoBV`](crate::NanoBV) of the given backing type, the first field being
+/// the most significant.
+///
+/// The widths must be constant expressions. Their total is checked against
+/// the backing type at compile time, and each value is checked against its
+/// width when the expansion is evaluated. The expansion only calls const fns,
+/// so it can be used in const contexts.
+#[macro_export]
+macro_rules! bv_concat {
+    ($type:ident; $($value:expr => $width:expr),+ $(,)?) => {{
+        const WIDTH: usize = 0 $(+ $width)+;
+        const _: () = assert!(WIDTH >= 1 && WIDTH <= ::core::mem::size_of::<$type>() * 8, "Invalid width provided.");
+        let builder = $crate::builder::NanoBVBuilder::new()$(.field($value as u64, $width))+;
+        $crate::NanoBV::<$type>::new(builder.value() as $type, WIDTH)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_finish() {
+        assert_eq!(NanoBVBuilder::new().finish(), None);
+        assert_eq!(
+            NanoBVBuilder::new().field(1, 1).finish(),
+            Some(Built::U8(NanoBV::<u8>::new(1, 1)))
+        );
+        let builder = NanoBVBuilder::new().field(0xAB, 8).field(0x5, 4);
+        assert_eq!((builder.len(), builder.value()), (12, 0xAB5));
+        assert_eq!(
+            builder.finish(),
+            Some(Built::U16(NanoBV::<u16>::new(0xAB5, 12)))
+        );
+        let wide = builder.field(0xFFFF_FFFF, 32);
+        assert_eq!(
+            wide.finish(),
+            Some(Built::U64(NanoBV::<u64>::new(0xAB5_FFFF_FFFF, 44)))
+        );
+        assert_eq!(
+            NanoBVBuilder::new().field(u64::MAX, 64).finish(),
+            Some(Built::U64(NanoBV::<u64>::ones(64)))
+        );
+        assert_eq!(
+            builder.field(0, 20).finish(),
+            Some(Built::U32(NanoBV::<u32>::new(0xAB5 << 20, 32)))
+        );
+    }
+
+    #[test]
+    fn test_builder_build() {
+        let builder = NanoBVBuilder::new()
+            .bits(NanoBV::<u8>::new(0b101, 3))
+            .bits(NanoBV::<u64>::new(0x7F, 7));
+        assert_eq!(
+            builder.build::<NanoBV<u16>>(),
+            Ok(NanoBV::<u16>::new(0b10_1111_1111, 10))
+        );
+        assert_eq!(builder.build::<NanoBV<u8>>(), Err(Error::InvalidLength));
+        assert_eq!(
+            NanoBVBuilder::default().build::<NanoBV<u8>>(),
+            Err(Error::InvalidLength)
+        );
+        assert!(NanoBVBuilder::new().is_empty());
+    }
+
+    #[test]
+    fn test_bv_concat() {
+        const WORD: NanoBV<u16> =
+            bv_concat!(u16; 0b1 => 1, 0x3 => 3, 0xAB => 8);
+        assert_eq!(WORD, NanoBV::<u16>::new(0b1011_1010_1011, 12));
+        let register = 5;
+        assert_eq!(
+            bv_concat!(u8; register => 3, 0 => 5,),
+            NanoBV::<u8>::new(0b1010_0000, 8)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_builder_value_overflow() {
+        NanoBVBuilder::new().field(0b100, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_builder_width_overflow() {
+        NanoBVBuilder::new().field(0, 60).field(0, 5);
+    }
+}
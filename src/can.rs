@@ -0,0 +1,156 @@
+//! CAN identifier packing.
+//!
+//! Standard identifiers are 11-bit NanoBVs and extended identifiers are
+//! 29-bit NanoBVs, both backed by `u32`. Extended identifiers can be split
+//! into their on-wire base and extension parts, or into the J1939 priority,
+//! parameter group number (PGN) and source address fields.
+
+use crate::{field::Field, NanoBV};
+
+/// Length of a standard CAN identifier.
+pub const STANDARD_ID_LENGTH: usize = 11;
+/// Length of an extended CAN identifier.
+pub const EXTENDED_ID_LENGTH: usize = 29;
+
+/// Base identifier of an extended CAN identifier, sent in place of a standard
+/// identifier.
+pub const BASE_ID: Field<u32> = Field::<u32>::new(18, 11);
+/// Identifier extension of an extended CAN identifier.
+pub const ID_EXTENSION: Field<u32> = Field::<u32>::new(0, 18);
+
+/// J1939 message priority.
+pub const J1939_PRIORITY: Field<u32> = Field::<u32>::new(26, 3);
+/// J1939 parameter group number, including the PDU specific byte.
+pub const J1939_PGN: Field<u32> = Field::<u32>::new(8, 18);
+/// J1939 PDU format byte.
+pub const J1939_PDU_FORMAT: Field<u32> = Field::<u32>::new(16, 8);
+/// J1939 PDU specific byte, the destination address of PDU1 messages.
+pub const J1939_PDU_SPECIFIC: Field<u32> = Field::<u32>::new(8, 8);
+/// J1939 source address.
+pub const J1939_SOURCE: Field<u32> = Field::<u32>::new(0, 8);
+
+impl NanoBV<u32> {
+    /// Create an 11-bit standard CAN identifier.
+    pub const fn can_standard_id(id: u32) -> Self {
+        assert!(id >> STANDARD_ID_LENGTH == 0, "Invalid identifier provided.");
+        NanoBV::<u32>::new(id, STANDARD_ID_LENGTH)
+    }
+
+    /// Create a 29-bit extended CAN identifier.
+    pub const fn can_extended_id(id: u32) -> Self {
+        assert!(id >> EXTENDED_ID_LENGTH == 0, "Invalid identifier provided.");
+        NanoBV::<u32>::new(id, EXTENDED_ID_LENGTH)
+    }
+
+    /// Create an extended CAN identifier from its base and extension parts.
+    pub const fn can_extended_from_parts(base: Self, extension: Self) -> Self {
+        assert!(
+            base.len() == BASE_ID.width()
+                && extension.len() == ID_EXTENSION.width(),
+            "Invalid length provided."
+        );
+        NanoBV::<u32>::zeros(EXTENDED_ID_LENGTH).apply_fields(&[
+            (BASE_ID, base.data),
+            (ID_EXTENSION, extension.data),
+        ])
+    }
+
+    /// Retrieve the 11-bit base identifier of an extended CAN identifier.
+    pub const fn can_base_id(&self) -> Self {
+        BASE_ID.read(*self)
+    }
+
+    /// Retrieve the 18-bit identifier extension of an extended CAN identifier.
+    pub const fn can_id_extension(&self) -> Self {
+        ID_EXTENSION.read(*self)
+    }
+
+    /// Create a J1939 extended CAN identifier.
+    ///
+    /// For PDU1 PGNs, whose PDU format is below 240, the PDU specific byte of
+    /// `pgn` is the destination address.
+    pub const fn j1939_id(priority: u8, pgn: u32, source: u8) -> Self {
+        assert!(
+            priority >> J1939_PRIORITY.width() == 0,
+            "Invalid priority provided."
+        );
+        assert!(pgn >> J1939_PGN.width() == 0, "Invalid PGN provided.");
+        NanoBV::<u32>::zeros(EXTENDED_ID_LENGTH).apply_fields(&[
+            (J1939_PRIORITY, priority as u32),
+            (J1939_PGN, pgn),
+            (J1939_SOURCE, source as u32),
+        ])
+    }
+
+    /// Retrieve the J1939 priority.
+    pub const fn j1939_priority(&self) -> u8 {
+        J1939_PRIORITY.read(*self).value() as u8
+    }
+
+    /// Retrieve the J1939 PGN, with the destination address of PDU1 messages
+    /// cleared.
+    pub const fn j1939_pgn(&self) -> u32 {
+        match self.j1939_destination() {
+            Some(_) => J1939_PGN.read(*self).value() & !0xFF,
+            None => J1939_PGN.read(*self).value(),
+        }
+    }
+
+    /// Retrieve the J1939 destination address, or `None` for PDU2 messages.
+    pub const fn j1939_destination(&self) -> Option<u8> {
+        match J1939_PDU_FORMAT.read(*self).value() {
+            0..=239 => Some(J1939_PDU_SPECIFIC.read(*self).value() as u8),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the J1939 source address.
+    pub const fn j1939_source(&self) -> u8 {
+        J1939_SOURCE.read(*self).value() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nanobv_can_ids() {
+        assert_eq!(
+            NanoBV::<u32>::can_standard_id(0x7FF),
+            NanoBV::<u32>::ones(11)
+        );
+        let id = NanoBV::<u32>::can_extended_id(0x18FE_F100);
+        assert_eq!(id.can_base_id(), NanoBV::<u32>::new(0x63F, 11));
+        assert_eq!(id.can_id_extension(), NanoBV::<u32>::new(0x2F100, 18));
+        assert_eq!(
+            NanoBV::<u32>::can_extended_from_parts(
+                id.can_base_id(),
+                id.can_id_extension()
+            ),
+            id
+        );
+    }
+
+    #[test]
+    fn test_nanobv_j1939_pdu2() {
+        let id = NanoBV::<u32>::j1939_id(6, 0xFEF1, 0x00);
+        assert_eq!(id, NanoBV::<u32>::can_extended_id(0x18FE_F100));
+        assert_eq!(
+            (id.j1939_priority(), id.j1939_pgn(), id.j1939_source()),
+            (6, 0xFEF1, 0x00)
+        );
+        assert_eq!(id.j1939_destination(), None);
+    }
+
+    #[test]
+    fn test_nanobv_j1939_pdu1() {
+        let id = NanoBV::<u32>::j1939_id(3, 0xEA17, 0xF9);
+        assert_eq!(id, NanoBV::<u32>::can_extended_id(0x0CEA_17F9));
+        assert_eq!(
+            (id.j1939_priority(), id.j1939_pgn(), id.j1939_source()),
+            (3, 0xEA00, 0xF9)
+        );
+        assert_eq!(id.j1939_destination(), Some(0x17));
+    }
+}
@@ -0,0 +1,305 @@
+//! Operations that never panic.
+//!
+//! Every method in this module reports invalid offsets, lengths and
+//! divisors through `None`, and overflow through `None`, wrapping or
+//! saturation at the result length, so none of them pull in the panic and
+//! formatting machinery. Binary operations keep the shorter length, and a
+//! result overflows when the exact value does not fit in that length.
+//!
+//! In release test builds, each method is additionally wrapped with
+//! `#[no_panic]` from the `no-panic` crate, which fails to link if the
+//! optimizer cannot remove every panic path:
+//!
+//! ```text
+//! cargo test --release checked
+//! ```
+
+use crate::NanoBV;
+use core::num::NonZeroU8;
+
+macro_rules! ImplNanoBVChecked {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVChecked!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Retrieve the shorter of both lengths.
+            const fn checked_shorter(&self, rhs: Self) -> NonZeroU8 {
+                match self.length.get() <= rhs.length.get() {
+                true => self.length,
+                false => rhs.length,
+                }
+            }
+
+            /// Create a NanoBV of `length` from `data`, or `None` if `data`
+            /// does not fit.
+            const fn checked_fit(data: Option<$type>, length: NonZeroU8) -> Option<Self> {
+                match data {
+                Some(data) if data & !Self::upper_bound(length) == 0 => Some(NanoBV { data, length }),
+                _ => None,
+                }
+            }
+
+            /// Create a NanoBV of `length` from `data`, truncating it.
+            const fn checked_masked(data: $type, length: NonZeroU8) -> Self {
+                NanoBV { data: data & Self::upper_bound(length), length }
+            }
+
+            /// Retrieve the bit at `offset`, or `None` if it is out of range.
+            pub const fn checked_get_bit(&self, offset: $type) -> Option<$type> {
+                match (offset as usize) < self.len() {
+                true => Some(self.data.wrapping_shr(offset as u32) & 1),
+                false => None,
+                }
+            }
+
+            /// Set the bit at `offset`, or `None` if it is out of range.
+            pub const fn checked_set_bit(&self, offset: $type) -> Option<Self> {
+                match (offset as usize) < self.len() {
+                true => Some(NanoBV { data: self.data | (1 as $type).wrapping_shl(offset as u32), length: self.length }),
+                false => None,
+                }
+            }
+
+            /// Clear the bit at `offset`, or `None` if it is out of range.
+            pub const fn checked_clear_bit(&self, offset: $type) -> Option<Self> {
+                match (offset as usize) < self.len() {
+                true => Some(NanoBV { data: self.data & !(1 as $type).wrapping_shl(offset as u32), length: self.length }),
+                false => None,
+                }
+            }
+
+            /// Extract bits `i` down to `j`, or `None` if `i` is out of range or
+            /// `j > i`.
+            pub const fn checked_extract(&self, i: usize, j: usize) -> Option<Self> {
+                if i >= self.len() || j > i {
+                    return None;
+                }
+                match NonZeroU8::new((i - j + 1) as u8) {
+                Some(length) => Some(Self::checked_masked(self.data.wrapping_shr(j as u32), length)),
+                None => None,
+                }
+            }
+
+            /// Concatenate `rhs` below the current NanoBV, or `None` if the result
+            /// does not fit in the backing type.
+            pub const fn checked_concat(&self, rhs: Self) -> Option<Self> {
+                let length = self.len() + rhs.len();
+                if length > Self::BIT_SIZE {
+                    return None;
+                }
+                match NonZeroU8::new(length as u8) {
+                Some(length) => Some(Self::checked_masked(self.data.wrapping_shl(rhs.len() as u32) | rhs.data, length)),
+                None => None,
+                }
+            }
+
+            /// Shift left by `amount`, or `None` if `amount` is not below the
+            /// length. Bits shifted past the length are discarded.
+            pub const fn checked_shl(&self, amount: usize) -> Option<Self> {
+                match amount < self.len() {
+                true => Some(Self::checked_masked(self.data.wrapping_shl(amount as u32), self.length)),
+                false => None,
+                }
+            }
+
+            /// Shift right by `amount`, or `None` if `amount` is not below the
+            /// length.
+            pub const fn checked_shr(&self, amount: usize) -> Option<Self> {
+                match amount < self.len() {
+                true => Some(Self::checked_masked(self.data.wrapping_shr(amount as u32), self.length)),
+                false => None,
+                }
+            }
+
+            /// Add, or `None` on overflow.
+            pub const fn checked_add(&self, rhs: Self) -> Option<Self> {
+                Self::checked_fit(self.data.checked_add(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Subtract, or `None` on overflow.
+            pub const fn checked_sub(&self, rhs: Self) -> Option<Self> {
+                Self::checked_fit(self.data.checked_sub(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Multiply, or `None` on overflow.
+            pub const fn checked_mul(&self, rhs: Self) -> Option<Self> {
+                Self::checked_fit(self.data.checked_mul(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Divide, or `None` if `rhs` is zero or the quotient overflows.
+            #[cfg(feature = "div")]
+            pub const fn checked_div(&self, rhs: Self) -> Option<Self> {
+                Self::checked_fit(self.data.checked_div(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Remainder, or `None` if `rhs` is zero or the remainder overflows.
+            #[cfg(feature = "div")]
+            pub const fn checked_rem(&self, rhs: Self) -> Option<Self> {
+                Self::checked_fit(self.data.checked_rem(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Add, wrapping at the shorter length.
+            pub const fn wrapping_add(&self, rhs: Self) -> Self {
+                Self::checked_masked(self.data.wrapping_add(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Subtract, wrapping at the shorter length.
+            pub const fn wrapping_sub(&self, rhs: Self) -> Self {
+                Self::checked_masked(self.data.wrapping_sub(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Multiply, wrapping at the shorter length.
+            pub const fn wrapping_mul(&self, rhs: Self) -> Self {
+                Self::checked_masked(self.data.wrapping_mul(rhs.data), self.checked_shorter(rhs))
+            }
+
+            /// Add one, wrapping to zero past the maximum value of the length.
+            pub const fn wrapping_inc(&self) -> Self {
+                Self::checked_masked(self.data.wrapping_add(1), self.length)
+            }
+
+            /// Subtract one, wrapping to the maximum value of the length below zero.
+            pub const fn wrapping_dec(&self) -> Self {
+                Self::checked_masked(self.data.wrapping_sub(1), self.length)
+            }
+
+            /// Add, saturating at the maximum value of the shorter length.
+            pub const fn saturating_add(&self, rhs: Self) -> Self {
+                match self.checked_add(rhs) {
+                Some(sum) => sum,
+                None => Self::checked_masked($type::MAX, self.checked_shorter(rhs)),
+                }
+            }
+
+            /// Subtract, saturating at zero, or at the maximum value of the shorter
+            /// length when the difference is positive but does not fit.
+            pub const fn saturating_sub(&self, rhs: Self) -> Self {
+                match self.checked_sub(rhs) {
+                Some(difference) => difference,
+                None if self.data < rhs.data => Self::checked_masked(0, self.checked_shorter(rhs)),
+                None => Self::checked_masked($type::MAX, self.checked_shorter(rhs)),
+                }
+            }
+        }
+    };
+}
+
+ImplNanoBVChecked!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplNanoBVCheckedTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVCheckedTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_checked_bits_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1010, 4);
+                assert_eq!(bv.checked_get_bit(1), Some(1));
+                assert_eq!(bv.checked_get_bit(4), None);
+                assert_eq!(bv.checked_set_bit(0), Some(NBV::new(0b1011, 4)));
+                assert_eq!(bv.checked_set_bit(<$type>::MAX), None);
+                assert_eq!(bv.checked_clear_bit(3), Some(NBV::new(0b0010, 4)));
+                assert_eq!(bv.checked_clear_bit(4), None);
+                assert_eq!(bv.checked_extract(2, 1), Some(NBV::new(0b01, 2)));
+                assert_eq!(bv.checked_extract(4, 1), None);
+                assert_eq!(bv.checked_extract(1, 2), None);
+                assert_eq!(bv.checked_concat(NBV::new(0b1, 1)), Some(NBV::new(0b10101, 5)));
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).checked_concat(bv), None);
+                assert_eq!(bv.checked_shl(1), Some(NBV::new(0b0100, 4)));
+                assert_eq!(bv.checked_shr(3), Some(NBV::new(0b0001, 4)));
+                assert_eq!(bv.checked_shl(4), None);
+                assert_eq!(bv.checked_shr(usize::MAX), None);
+            }
+
+            #[test]
+            fn [<test_nanobv_checked_arithmetic_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (a, b) = (NBV::new(0b1100, 4), NBV::new(0b0101, 4));
+                assert_eq!(a.checked_add(b), None);
+                assert_eq!(b.checked_add(b), Some(NBV::new(0b1010, 4)));
+                assert_eq!(a.checked_sub(b), Some(NBV::new(0b0111, 4)));
+                assert_eq!(b.checked_sub(a), None);
+                assert_eq!(a.checked_mul(b), None);
+                assert_eq!(NBV::new(3, 4).checked_mul(b), Some(NBV::new(15, 4)));
+                #[cfg(feature = "div")]
+                {
+                    assert_eq!(a.checked_div(b), Some(NBV::new(2, 4)));
+                    assert_eq!(a.checked_div(NBV::zeros(4)), None);
+                    assert_eq!(a.checked_rem(b), Some(NBV::new(2, 4)));
+                    assert_eq!(a.checked_rem(NBV::zeros(4)), None);
+                    assert_eq!(NBV::new(0xF0, 8).checked_div(NBV::new(1, 4)), None);
+                }
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).checked_add(NBV::new(1, NBV::BIT_SIZE)), None);
+                assert_eq!(a.wrapping_add(b), NBV::new(0b0001, 4));
+                assert_eq!(b.wrapping_sub(a), NBV::new(0b1001, 4));
+                assert_eq!(a.wrapping_mul(b), NBV::new(0b1100, 4));
+                assert_eq!(NBV::new(0b1110, 4).wrapping_inc(), NBV::ones(4));
+                assert_eq!(NBV::ones(4).wrapping_inc(), NBV::zeros(4));
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).wrapping_inc(), NBV::zeros(NBV::BIT_SIZE));
+                assert_eq!(NBV::zeros(4).wrapping_dec(), NBV::ones(4));
+                assert_eq!(NBV::new(0b0101, 4).wrapping_dec(), NBV::new(0b0100, 4));
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).wrapping_add(NBV::new(1, NBV::BIT_SIZE)), NBV::zeros(NBV::BIT_SIZE));
+                assert_eq!(a.saturating_add(b), NBV::ones(4));
+                assert_eq!(b.saturating_sub(a), NBV::zeros(4));
+                assert_eq!(NBV::new(0xF0, 8).saturating_sub(NBV::new(1, 4)), NBV::ones(4));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVCheckedTest!(for u8, u16, u32, u64);
+
+    #[cfg(all(not(debug_assertions), feature = "div"))]
+    mod no_panic {
+        use crate::NanoBV;
+        use no_panic::no_panic;
+        use paste::paste;
+
+        macro_rules! ImplNoPanicTest {
+            (for $($type:tt),+) => {
+                $(ImplNoPanicTest!($type);)*
+            };
+
+            ($type:ident) => {
+            paste! {
+                #[no_panic]
+                fn [<bits_ $type>](bv: NanoBV<$type>, offset: $type, i: usize, j: usize) -> Option<NanoBV<$type>> {
+                    bv.checked_get_bit(offset)?;
+                    bv.checked_set_bit(offset)?.checked_clear_bit(offset)?.checked_extract(i, j)?.checked_concat(bv)?.checked_shl(i)?.checked_shr(j)
+                }
+
+                #[no_panic]
+                fn [<arithmetic_ $type>](a: NanoBV<$type>, b: NanoBV<$type>) -> Option<NanoBV<$type>> {
+                    a.checked_add(b)?.checked_sub(b)?.checked_mul(b)?.checked_div(b)?.checked_rem(b)
+                }
+
+                #[no_panic]
+                fn [<wrapping_ $type>](a: NanoBV<$type>, b: NanoBV<$type>) -> NanoBV<$type> {
+                    a.wrapping_add(b).wrapping_sub(b).wrapping_mul(b).saturating_add(b).saturating_sub(b).wrapping_inc().wrapping_dec()
+                }
+
+                #[test]
+                fn [<test_nanobv_checked_no_panic_ $type>]() {
+                    let bv = core::hint::black_box(NanoBV::<$type>::new(0b1010, 4));
+                    assert_eq!([<bits_ $type>](bv, 1, 2, 1), Some(NanoBV::<$type>::new(0b1_0100, 6)));
+                    assert_eq!([<arithmetic_ $type>](bv, bv), None);
+                    assert_eq!([<arithmetic_ $type>](bv, NanoBV::<$type>::new(1, 4)), Some(NanoBV::<$type>::new(0, 4)));
+                    assert_eq!([<wrapping_ $type>](bv, bv), NanoBV::<$type>::new(0b0100, 4));
+                }
+            }
+            };
+        }
+
+        ImplNoPanicTest!(for u8, u16, u32, u64);
+    }
+}
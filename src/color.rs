@@ -0,0 +1,159 @@
+//! Color channel packing.
+//!
+//! Channels are given and returned as 8-bit values. Packing keeps the high
+//! bits of each channel, and unpacking replicates them into the low bits so
+//! that full intensity stays at `0xFF`.
+
+use crate::{field::Field, NanoBV};
+
+/// Red channel of an RGB565 pixel.
+pub const RGB565_R: Field<u16> = Field::<u16>::new(11, 5);
+/// Green channel of an RGB565 pixel.
+pub const RGB565_G: Field<u16> = Field::<u16>::new(5, 6);
+/// Blue channel of an RGB565 pixel.
+pub const RGB565_B: Field<u16> = Field::<u16>::new(0, 5);
+
+/// Red channel of an RGB332 pixel.
+pub const RGB332_R: Field<u8> = Field::<u8>::new(5, 3);
+/// Green channel of an RGB332 pixel.
+pub const RGB332_G: Field<u8> = Field::<u8>::new(2, 3);
+/// Blue channel of an RGB332 pixel.
+pub const RGB332_B: Field<u8> = Field::<u8>::new(0, 2);
+
+/// Alpha channel of an ARGB8888 pixel.
+pub const ARGB8888_A: Field<u32> = Field::<u32>::new(24, 8);
+/// Red channel of an ARGB8888 pixel.
+pub const ARGB8888_R: Field<u32> = Field::<u32>::new(16, 8);
+/// Green channel of an ARGB8888 pixel.
+pub const ARGB8888_G: Field<u32> = Field::<u32>::new(8, 8);
+/// Blue channel of an ARGB8888 pixel.
+pub const ARGB8888_B: Field<u32> = Field::<u32>::new(0, 8);
+
+/// Narrow an 8-bit channel to `width` bits, keeping the high bits.
+pub const fn narrow_channel(value: u8, width: usize) -> u8 {
+    assert!(width >= 1 && width <= 8, "Invalid width provided.");
+    value >> (8 - width)
+}
+
+/// Widen a `width`-bit channel to 8 bits, replicating its bits downwards.
+pub const fn widen_channel(value: u8, width: usize) -> u8 {
+    assert!(width >= 1 && width <= 8, "Invalid width provided.");
+    let (mut widened, mut filled) = (0u16, 0);
+    while filled < 8 {
+        widened = (widened << width) | value as u16;
+        filled += width;
+    }
+    (widened >> (filled - 8)) as u8
+}
+
+impl NanoBV<u16> {
+    /// Pack 8-bit channels into an RGB565 pixel.
+    pub const fn rgb565(r: u8, g: u8, b: u8) -> Self {
+        NanoBV::<u16>::default().apply_fields(&[
+            (RGB565_R, narrow_channel(r, 5) as u16),
+            (RGB565_G, narrow_channel(g, 6) as u16),
+            (RGB565_B, narrow_channel(b, 5) as u16),
+        ])
+    }
+
+    /// Unpack an RGB565 pixel into 8-bit `(r, g, b)` channels.
+    pub const fn unpack_rgb565(&self) -> (u8, u8, u8) {
+        (
+            widen_channel(RGB565_R.read(*self).value() as u8, 5),
+            widen_channel(RGB565_G.read(*self).value() as u8, 6),
+            widen_channel(RGB565_B.read(*self).value() as u8, 5),
+        )
+    }
+}
+
+impl NanoBV<u8> {
+    /// Pack 8-bit channels into an RGB332 pixel.
+    pub const fn rgb332(r: u8, g: u8, b: u8) -> Self {
+        NanoBV::<u8>::default().apply_fields(&[
+            (RGB332_R, narrow_channel(r, 3)),
+            (RGB332_G, narrow_channel(g, 3)),
+            (RGB332_B, narrow_channel(b, 2)),
+        ])
+    }
+
+    /// Unpack an RGB332 pixel into 8-bit `(r, g, b)` channels.
+    pub const fn unpack_rgb332(&self) -> (u8, u8, u8) {
+        (
+            widen_channel(RGB332_R.read(*self).value(), 3),
+            widen_channel(RGB332_G.read(*self).value(), 3),
+            widen_channel(RGB332_B.read(*self).value(), 2),
+        )
+    }
+}
+
+impl NanoBV<u32> {
+    /// Pack 8-bit channels into an ARGB8888 pixel.
+    pub const fn argb8888(a: u8, r: u8, g: u8, b: u8) -> Self {
+        NanoBV::<u32>::default().apply_fields(&[
+            (ARGB8888_A, a as u32),
+            (ARGB8888_R, r as u32),
+            (ARGB8888_G, g as u32),
+            (ARGB8888_B, b as u32),
+        ])
+    }
+
+    /// Unpack an ARGB8888 pixel into `(a, r, g, b)` channels.
+    pub const fn unpack_argb8888(&self) -> (u8, u8, u8, u8) {
+        (
+            ARGB8888_A.read(*self).value() as u8,
+            ARGB8888_R.read(*self).value() as u8,
+            ARGB8888_G.read(*self).value() as u8,
+            ARGB8888_B.read(*self).value() as u8,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_widen_narrow() {
+        assert_eq!(widen_channel(0b11111, 5), 0xFF);
+        assert_eq!(widen_channel(0b10000, 5), 0b1000_0100);
+        assert_eq!(widen_channel(0b01, 2), 0b0101_0101);
+        assert_eq!(widen_channel(0b101, 3), 0b1011_0110);
+        assert_eq!(widen_channel(0, 6), 0);
+        assert_eq!(narrow_channel(0xFF, 5), 0b11111);
+        assert_eq!(narrow_channel(0b1011_0110, 3), 0b101);
+        for width in 1..=8 {
+            for value in 0..(1u16 << width) {
+                let value = value as u8;
+                assert_eq!(
+                    narrow_channel(widen_channel(value, width), width),
+                    value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_nanobv_rgb565() {
+        let pixel = NanoBV::<u16>::rgb565(0xFF, 0x80, 0x00);
+        assert_eq!(pixel, NanoBV::<u16>::new(0xFC00, 16));
+        assert_eq!(pixel.unpack_rgb565(), (0xFF, 0x82, 0x00));
+        assert_eq!(
+            NanoBV::<u16>::rgb565(0xFF, 0xFF, 0xFF).unpack_rgb565(),
+            (0xFF, 0xFF, 0xFF)
+        );
+    }
+
+    #[test]
+    fn test_nanobv_rgb332() {
+        let pixel = NanoBV::<u8>::rgb332(0xFF, 0x40, 0xC0);
+        assert_eq!(pixel, NanoBV::<u8>::new(0b1110_1011, 8));
+        assert_eq!(pixel.unpack_rgb332(), (0xFF, 0x49, 0xFF));
+    }
+
+    #[test]
+    fn test_nanobv_argb8888() {
+        let pixel = NanoBV::<u32>::argb8888(0x80, 0x12, 0x34, 0x56);
+        assert_eq!(pixel, NanoBV::<u32>::new(0x8012_3456, 32));
+        assert_eq!(pixel.unpack_argb8888(), (0x80, 0x12, 0x34, 0x56));
+    }
+}
@@ -0,0 +1,360 @@
+//! Cyclic redundancy checks described by Rocksoft model parameters.
+//!
+//! [`Algorithm`] uses the parameter names of the CRC RevEng catalogue and the
+//! `crc-catalog` crate, so catalogue entries can be copied verbatim. With the
+//! `crc-catalog` feature enabled, `crc_catalog::Algorithm` (also re-exported
+//! by the `crc` crate as `crc::Algorithm`) converts to and from [`Algorithm`].
+//!
+//! ```
+//! use nanobv::{crc::{Crc, CRC_16_IBM_3740}, NanoBV};
+//!
+//! let crc = Crc::<u16>::new(CRC_16_IBM_3740);
+//! assert_eq!(crc.checksum(b"123456789"), NanoBV::<u16>::new(0x29B1, 16));
+//!
+//! let mut digest = crc.digest();
+//! digest.update(b"1234");
+//! digest.update(b"56789");
+//! assert_eq!(digest.finalize(), crc.checksum(b"123456789"));
+//! ```
+
+use crate::NanoBV;
+
+/// CRC parameters in the Rocksoft model.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Algorithm<T = u32> {
+    /// Degree of the generator polynomial, i.e. the number of bits in the
+    /// CRC.
+    pub width: u8,
+    /// Generator polynomial without the highest-order term.
+    pub poly: T,
+    /// Register contents before the first message bit.
+    pub init: T,
+    /// Whether message bytes are read least significant bit first.
+    pub refin: bool,
+    /// Whether the register is reflected before `xorout` is applied.
+    pub refout: bool,
+    /// Value XORed into the register to produce the CRC.
+    pub xorout: T,
+    /// CRC of the ASCII string `123456789`.
+    pub check: T,
+    /// Register contents after processing a message followed by its CRC,
+    /// before `xorout` is applied.
+    pub residue: T,
+}
+
+/// CRC-5/USB.
+pub const CRC_5_USB: Algorithm<u8> = Algorithm {
+    width: 5,
+    poly: 0x05,
+    init: 0x1F,
+    refin: true,
+    refout: true,
+    xorout: 0x1F,
+    check: 0x19,
+    residue: 0x06,
+};
+/// CRC-8/SMBUS.
+pub const CRC_8_SMBUS: Algorithm<u8> = Algorithm {
+    width: 8,
+    poly: 0x07,
+    init: 0x00,
+    refin: false,
+    refout: false,
+    xorout: 0x00,
+    check: 0xF4,
+    residue: 0x00,
+};
+/// CRC-15/CAN.
+pub const CRC_15_CAN: Algorithm<u16> = Algorithm {
+    width: 15,
+    poly: 0x4599,
+    init: 0x0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x059E,
+    residue: 0x0000,
+};
+/// CRC-16/IBM-3740, also known as CRC-16/CCITT-FALSE.
+pub const CRC_16_IBM_3740: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0xFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x29B1,
+    residue: 0x0000,
+};
+/// CRC-16/KERMIT, also known as CRC-16/CCITT.
+pub const CRC_16_KERMIT: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0x2189,
+    residue: 0x0000,
+};
+/// CRC-16/XMODEM.
+pub const CRC_16_XMODEM: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x31C3,
+    residue: 0x0000,
+};
+/// CRC-16/MODBUS.
+pub const CRC_16_MODBUS: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x8005,
+    init: 0xFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0x4B37,
+    residue: 0x0000,
+};
+/// CRC-32/ISO-HDLC, the CRC of Ethernet, zlib and PNG.
+pub const CRC_32_ISO_HDLC: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x04C1_1DB7,
+    init: 0xFFFF_FFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFF_FFFF,
+    check: 0xCBF4_3926,
+    residue: 0xDEBB_20E3,
+};
+/// CRC-32/ISCSI, also known as CRC-32C.
+pub const CRC_32_ISCSI: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x1EDC_6F41,
+    init: 0xFFFF_FFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFF_FFFF,
+    check: 0xE306_9283,
+    residue: 0xB798_B438,
+};
+/// CRC-32/BZIP2.
+pub const CRC_32_BZIP2: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x04C1_1DB7,
+    init: 0xFFFF_FFFF,
+    refin: false,
+    refout: false,
+    xorout: 0xFFFF_FFFF,
+    check: 0xFC89_1918,
+    residue: 0xC704_DD7B,
+};
+/// CRC-64/XZ.
+pub const CRC_64_XZ: Algorithm<u64> = Algorithm {
+    width: 64,
+    poly: 0x42F0_E1EB_A9EA_3693,
+    init: 0xFFFF_FFFF_FFFF_FFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFF_FFFF_FFFF_FFFF,
+    check: 0x995D_C9BB_DF19_39FA,
+    residue: 0x4995_8C9A_BD7D_353F,
+};
+/// CRC-64/ECMA-182.
+pub const CRC_64_ECMA_182: Algorithm<u64> = Algorithm {
+    width: 64,
+    poly: 0x42F0_E1EB_A9EA_3693,
+    init: 0x0000_0000_0000_0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000_0000_0000_0000,
+    check: 0x6C40_DF5F_0B49_7347,
+    residue: 0x0000_0000_0000_0000,
+};
+
+/// CRC engine for an [`Algorithm`].
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Crc<T = u32> {
+    algorithm: Algorithm<T>,
+}
+
+/// Running CRC computation, created by [`Crc::digest`].
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Digest<T = u32> {
+    crc: Crc<T>,
+    register: u64,
+}
+
+impl<T: Copy> Crc<T> {
+    /// Retrieve the parameters of the CRC.
+    pub const fn algorithm(&self) -> Algorithm<T> {
+        self.algorithm
+    }
+}
+
+/// Shift `byte` into a register whose CRC bits are aligned to the most
+/// significant end of a `u64`.
+const fn crc_update_byte(register: u64, poly: u64, byte: u8) -> u64 {
+    let (mut register, mut i) = (register ^ ((byte as u64) << 56), 0);
+    while i < 8 {
+        register = (register << 1) ^ (poly & (register >> 63).wrapping_neg());
+        i += 1;
+    }
+    register
+}
+
+macro_rules! ImplCrc {
+    (for $($type:tt),+) => {
+        $(ImplCrc!($type);)*
+    };
+
+    ($type:ident) => {
+        impl Crc<$type> {
+            /// Create a [`Crc`] engine for `algorithm`.
+            pub const fn new(algorithm: Algorithm<$type>) -> Self {
+                ["Invalid width provided."][(algorithm.width == 0 || algorithm.width as usize > NanoBV::<$type>::BIT_SIZE) as usize];
+                Crc { algorithm }
+            }
+
+            /// Retrieve the register, shifted to the most significant end of a
+            /// `u64`.
+            const fn crc_aligned(&self, value: $type) -> u64 {
+                (value as u64) << (64 - self.algorithm.width)
+            }
+
+            /// Feed `bytes` into an aligned register.
+            const fn crc_update(&self, mut register: u64, bytes: &[u8]) -> u64 {
+                let (poly, mut i) = (self.crc_aligned(self.algorithm.poly), 0);
+                while i < bytes.len() {
+                    let byte = match self.algorithm.refin {
+                    true => bytes[i].reverse_bits(),
+                    false => bytes[i],
+                    };
+                    register = crc_update_byte(register, poly, byte);
+                    i += 1;
+                }
+                register
+            }
+
+            /// Produce the CRC from an aligned register.
+            const fn crc_finalize(&self, register: u64) -> NanoBV<$type> {
+                let width = self.algorithm.width as usize;
+                let crc = match self.algorithm.refout {
+                true => register.reverse_bits(),
+                false => register >> (64 - width),
+                };
+                NanoBV::<$type>::new(crc as $type ^ self.algorithm.xorout, width)
+            }
+
+            /// Compute the CRC of `bytes`.
+            pub const fn checksum(&self, bytes: &[u8]) -> NanoBV<$type> {
+                self.crc_finalize(self.crc_update(self.crc_aligned(self.algorithm.init), bytes))
+            }
+
+            /// Start a running CRC computation.
+            pub const fn digest(&self) -> Digest<$type> {
+                Digest { crc: *self, register: self.crc_aligned(self.algorithm.init) }
+            }
+        }
+
+        impl Digest<$type> {
+            /// Feed `bytes` into the CRC.
+            pub fn update(&mut self, bytes: &[u8]) {
+                self.register = self.crc.crc_update(self.register, bytes);
+            }
+
+            /// Feed a single message bit into the CRC.
+            pub fn update_bit(&mut self, bit: bool) {
+                let register = self.register ^ ((bit as u64) << 63);
+                self.register = (register << 1) ^ (self.crc.crc_aligned(self.crc.algorithm.poly) & (register >> 63).wrapping_neg());
+            }
+
+            /// Retrieve the CRC of the bytes fed so far.
+            pub const fn finalize(&self) -> NanoBV<$type> {
+                self.crc.crc_finalize(self.register)
+            }
+        }
+
+        #[cfg(feature = "crc-catalog")]
+        impl From<crc_catalog::Algorithm<$type>> for Algorithm<$type> {
+            fn from(algorithm: crc_catalog::Algorithm<$type>) -> Self {
+                Algorithm {
+                    width: algorithm.width,
+                    poly: algorithm.poly,
+                    init: algorithm.init,
+                    refin: algorithm.refin,
+                    refout: algorithm.refout,
+                    xorout: algorithm.xorout,
+                    check: algorithm.check,
+                    residue: algorithm.residue,
+                }
+            }
+        }
+
+        #[cfg(feature = "crc-catalog")]
+        impl From<Algorithm<$type>> for crc_catalog::Algorithm<$type> {
+            fn from(algorithm: Algorithm<$type>) -> Self {
+                crc_catalog::Algorithm {
+                    width: algorithm.width,
+                    poly: algorithm.poly,
+                    init: algorithm.init,
+                    refin: algorithm.refin,
+                    refout: algorithm.refout,
+                    xorout: algorithm.xorout,
+                    check: algorithm.check,
+                    residue: algorithm.residue,
+                }
+            }
+        }
+    };
+}
+
+ImplCrc!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplCrcTest {
+        (for $(($type:ident, $($algorithm:ident),+)),+) => {
+        paste! {
+            $(#[test]
+            fn [<test_crc_check_ $type>]() {
+                $(let crc = Crc::<$type>::new($algorithm);
+                assert_eq!(crc.checksum(b"123456789"), NanoBV::<$type>::new($algorithm.check, $algorithm.width as usize), stringify!($algorithm));
+                let mut digest = crc.digest();
+                digest.update(b"12");
+                digest.update(b"");
+                digest.update(b"3456789");
+                assert_eq!(digest.finalize(), crc.checksum(b"123456789"), stringify!($algorithm));)+
+            })+
+        }
+        };
+    }
+
+    ImplCrcTest!(for (u8, CRC_5_USB, CRC_8_SMBUS), (u16, CRC_15_CAN, CRC_16_IBM_3740, CRC_16_KERMIT, CRC_16_XMODEM, CRC_16_MODBUS), (u32, CRC_32_ISO_HDLC, CRC_32_ISCSI, CRC_32_BZIP2), (u64, CRC_64_XZ, CRC_64_ECMA_182));
+
+    #[test]
+    fn test_crc_empty() {
+        let crc = Crc::<u32>::new(CRC_32_ISO_HDLC);
+        assert_eq!(crc.checksum(b""), NanoBV::<u32>::zeros(32));
+        assert_eq!(crc.digest().finalize(), crc.checksum(b""));
+        assert_eq!(crc.algorithm(), CRC_32_ISO_HDLC);
+    }
+
+    #[cfg(feature = "crc-catalog")]
+    #[test]
+    fn test_crc_catalog_interop() {
+        assert_eq!(
+            Algorithm::from(crc_catalog::CRC_16_IBM_3740),
+            CRC_16_IBM_3740
+        );
+        assert_eq!(Algorithm::from(crc_catalog::CRC_32_ISCSI), CRC_32_ISCSI);
+        let algorithm: crc_catalog::Algorithm<u64> = CRC_64_XZ.into();
+        assert_eq!(algorithm.check, crc_catalog::CRC_64_XZ.check);
+    }
+}
@@ -0,0 +1,66 @@
+//! Instruction field extraction for fixed-width encodings.
+//!
+//! ```
+//! use nanobv::{bv_fields, NanoBV};
+//!
+//! // MIPS `addiu $t0, $sp, -16`.
+//! const WORD: NanoBV<u32> = NanoBV::<u32>::new(0x27A8_FFF0, 32);
+//! const FIELDS: (NanoBV<u32>, NanoBV<u32>, NanoBV<u32>, i64) =
+//!     bv_fields!(WORD; opcode: 31..26, rs: 25..21, rt: 20..16, imm: 15..0 signed);
+//!
+//! assert_eq!(FIELDS.0, NanoBV::<u32>::new(0x09, 6));
+//! assert_eq!(FIELDS.1, NanoBV::<u32>::new(29, 5));
+//! assert_eq!(FIELDS.2, NanoBV::<u32>::new(8, 5));
+//! assert_eq!(FIELDS.3, -16);
+//! ```
+
+/// Extract several bit ranges of a [`NanoBV`](crate::NanoBV) at once.
+///
+/// Each field is written `name: hi..lo`, with both bounds inclusive as in
+/// [`NanoBV::extract`](crate::NanoBV::extract), and evaluates to a
+/// [`NanoBV`](crate::NanoBV) of length `hi - lo + 1`. A field followed by
+/// `signed` evaluates to its two's complement value as an `i64` instead. The
+/// names only document the layout; the fields are returned as a tuple in the
+/// order given.
+///
+/// The expansion only calls const fns, so it can be used in const contexts.
+#[macro_export]
+macro_rules! bv_fields {
+    ($word:expr; $($name:ident : $hi:tt .. $lo:tt $($signed:ident)?),+ $(,)?) => {{
+        let word = $word;
+        ($($crate::bv_fields!(@field word, $hi, $lo $(, $signed)?),)+)
+    }};
+
+    (@field $word:ident, $hi:tt, $lo:tt) => {
+        $word.extract($hi, $lo)
+    };
+
+    (@field $word:ident, $hi:tt, $lo:tt, signed) => {
+        $word.extract($hi, $lo).signed_value()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::NanoBV;
+
+    #[test]
+    fn test_bv_fields() {
+        let word = NanoBV::<u32>::new(0x0062_1820, 32);
+        let (opcode, rs, rt, rd, shamt, funct) = bv_fields!(word; opcode: 31..26, rs: 25..21, rt: 20..16, rd: 15..11, shamt: 10..6, funct: 5..0);
+        assert_eq!(opcode, NanoBV::<u32>::zeros(6));
+        assert_eq!((rs.value(), rt.value(), rd.value()), (3, 2, 3));
+        assert_eq!(shamt, NanoBV::<u32>::zeros(5));
+        assert_eq!(funct, NanoBV::<u32>::new(0x20, 6));
+    }
+
+    #[test]
+    fn test_bv_fields_signed() {
+        let word = NanoBV::<u16>::new(0xA5F0, 16);
+        let (high, low) =
+            bv_fields!(word; high: 15..8 signed, low: 7..0 signed,);
+        assert_eq!((high, low), (-91, -16));
+        let (nibble,) = bv_fields!(word; nibble: 11..8);
+        assert_eq!(nibble, NanoBV::<u16>::new(5, 4));
+    }
+}
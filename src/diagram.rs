@@ -0,0 +1,122 @@
+//! RFC-style bit-layout diagrams.
+//!
+//! Each bit takes three columns, most significant bit first. Bits covered by
+//! a field are labeled with its name and its value in hexadecimal, without
+//! the `0x` prefix when it does not fit, and bits outside of every field are
+//! left blank.
+//!
+//! ```
+//! use nanobv::{field::Field, NanoBV};
+//!
+//! let fields = [("OP", Field::<u8>::new(5, 3)), ("RD", Field::<u8>::new(0, 5))];
+//! let diagram = NanoBV::<u8>::new(0b101_00011, 8).diagram(&fields);
+//! assert_eq!(
+//!     diagram,
+//!     concat!(
+//!         "  7  6  5  4  3  2  1  0\n",
+//!         "+--------+--------------+\n",
+//!         "|   OP   |      RD      |\n",
+//!         "|  0x5   |     0x3      |\n",
+//!         "+--------+--------------+\n",
+//!     )
+//! );
+//! ```
+
+use crate::{field::Field, NanoBV};
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Append `text` centered in `width` columns, truncated if too long.
+fn push_centered(line: &mut String, text: &str, width: usize) {
+    let text = text.get(..width).unwrap_or(text);
+    let padding = width - text.len();
+    let _ = write!(
+        line,
+        "{:left$}{}{:right$}",
+        "",
+        text,
+        "",
+        left = padding / 2,
+        right = padding - padding / 2
+    );
+}
+
+macro_rules! ImplNanoBVDiagram {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVDiagram!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Render the current NanoBV as a bit-layout diagram of the named
+            /// `fields`. Where fields overlap, the first one is shown.
+            pub fn diagram(&self, fields: &[(&str, Field<$type>)]) -> String {
+                let field_at = |bit: usize| fields.iter().position(|(_, field)| (field.offset()..field.offset() + field.width()).contains(&bit));
+                let (mut ruler, mut border, mut names, mut values) = (String::new(), String::from("+"), String::new(), String::new());
+                for bit in (0..self.len()).rev() {
+                    let _ = write!(ruler, " {:>2}", bit);
+                }
+                let mut end = self.len();
+                while end > 0 {
+                    let current = field_at(end - 1);
+                    let mut start = end - 1;
+                    while start > 0 && field_at(start - 1) == current {
+                        start -= 1;
+                    }
+                    let width = 3 * (end - start) - 1;
+                    names.push('|');
+                    values.push('|');
+                    match current {
+                    Some(index) => {
+                        let (name, field) = fields[index];
+                        let mut hex = String::new();
+                        let _ = write!(hex, "{:#x}", field.read(*self).value());
+                        if hex.len() > width {
+                            hex.replace_range(..2, "");
+                        }
+                        push_centered(&mut names, name, width);
+                        push_centered(&mut values, &hex, width);
+                    }
+                    None => {
+                        push_centered(&mut names, "", width);
+                        push_centered(&mut values, "", width);
+                    }
+                    }
+                    border.extend(core::iter::repeat('-').take(width));
+                    border.push('+');
+                    end = start;
+                }
+                let mut diagram = String::new();
+                let _ = write!(diagram, "{}\n{}\n{}|\n{}|\n{}\n", ruler, border, names, values, border);
+                diagram
+            }
+        }
+    };
+}
+
+ImplNanoBVDiagram!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nanobv_diagram() {
+        let fields = [
+            ("VERSION", Field::<u16>::new(12, 4)),
+            ("FLAGS", Field::<u16>::new(4, 3)),
+            ("X", Field::<u16>::new(0, 1)),
+        ];
+        let diagram = NanoBV::<u16>::new(0x6051, 16).diagram(&fields);
+        assert_eq!(
+            diagram,
+            concat!(
+                " 15 14 13 12 11 10  9  8  7  6  5  4  3  2  1  0\n",
+                "+-----------+--------------+--------+--------+--+\n",
+                "|  VERSION  |              | FLAGS  |        |X |\n",
+                "|    0x6    |              |  0x5   |        |1 |\n",
+                "+-----------+--------------+--------+--------+--+\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Register diffs reporting which bits and fields changed, and patches
+//! replaying them.
+
+use crate::{field::Field, NanoBV, SetBits};
+
+/// Difference between two values of a register.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Diff<T = u32> {
+    before: NanoBV<T>,
+    after: NanoBV<T>,
+}
+
+impl<T: Copy> Diff<T> {
+    /// Retrieve the value before the change.
+    pub const fn before(&self) -> NanoBV<T> {
+        self.before
+    }
+
+    /// Retrieve the value after the change.
+    pub const fn after(&self) -> NanoBV<T> {
+        self.after
+    }
+}
+
+/// Changed bits of a [`Diff`] and their new values, replayable with
+/// [`NanoBV::apply`].
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct BitPatch<T = u32> {
+    mask: NanoBV<T>,
+    values: NanoBV<T>,
+}
+
+impl<T: Copy> BitPatch<T> {
+    /// Retrieve the mask of the patched bits.
+    pub const fn mask(&self) -> NanoBV<T> {
+        self.mask
+    }
+
+    /// Retrieve the new values of the patched bits, zero elsewhere.
+    pub const fn values(&self) -> NanoBV<T> {
+        self.values
+    }
+}
+
+macro_rules! ImplDiff {
+    (for $($type:tt),+) => {
+        $(ImplDiff!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Compute the [`Diff`] from the current NanoBV to `other`.
+            pub const fn diff(&self, other: Self) -> Diff<$type> {
+                Diff { before: *self, after: other }
+            }
+
+            /// Apply `patch`, setting the patched bits to their new values and
+            /// taking the length of the patch.
+            pub const fn apply(&self, patch: BitPatch<$type>) -> Self {
+                NanoBV::<$type>::new((self.data & !patch.mask.data) | patch.values.data, patch.mask.len())
+            }
+        }
+
+        impl Diff<$type> {
+            /// Retrieve the changed bits, i.e. the XOR of both values.
+            pub const fn changed(&self) -> NanoBV<$type> {
+                self.before.bvxor(self.after)
+            }
+
+            /// Retrieve the bits that changed from 0 to 1.
+            pub const fn rising(&self) -> NanoBV<$type> {
+                self.changed().bvand(self.after)
+            }
+
+            /// Retrieve the bits that changed from 1 to 0.
+            pub const fn falling(&self) -> NanoBV<$type> {
+                self.changed().bvand(self.before)
+            }
+
+            /// Check whether any bit changed.
+            pub const fn is_changed(&self) -> bool {
+                self.changed().value() != 0
+            }
+
+            /// Iterate over the offsets of the changed bits.
+            pub const fn changed_bits(&self) -> SetBits<$type> {
+                self.changed().iter_set_bits()
+            }
+
+            /// Iterate over the fields of `fields` containing a changed bit.
+            pub fn changed_fields<'a>(&self, fields: &'a [Field<$type>]) -> impl Iterator<Item = &'a Field<$type>> + 'a {
+                let changed = self.changed().value();
+                fields.iter().filter(move |field| changed & field.mask() != 0)
+            }
+
+            /// Record the changed bits and their new values as a [`BitPatch`] of the
+            /// length of the new value, zero-extending the old one if shorter.
+            pub const fn patch(&self) -> BitPatch<$type> {
+                let mask = NanoBV::<$type>::new(self.before.data ^ self.after.data, self.after.len());
+                BitPatch { mask, values: mask.bvand(self.after) }
+            }
+        }
+
+        impl BitPatch<$type> {
+            /// Create a new [`BitPatch`] setting the bits in `mask` to `values`.
+            ///
+            /// Bits of `values` outside of `mask` are cleared.
+            pub const fn new(mask: NanoBV<$type>, values: NanoBV<$type>) -> Self {
+                ["Invalid length provided."][(mask.len() != values.len()) as usize];
+                BitPatch { mask, values: values.bvand(mask) }
+            }
+
+            /// Check whether the patch changes no bits.
+            pub const fn is_empty(&self) -> bool {
+                self.mask.value() == 0
+            }
+
+            /// Iterate over the offsets of the patched bits.
+            pub const fn positions(&self) -> SetBits<$type> {
+                self.mask.iter_set_bits()
+            }
+        }
+    };
+}
+
+ImplDiff!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplDiffTest {
+        (for $($type:tt),+) => {
+            $(ImplDiffTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_diff_changed_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let diff = NBV::new(0b1100_1010, 8).diff(NBV::new(0b1010_1010, 8));
+                assert_eq!(diff.changed(), NBV::new(0b0110_0000, 8));
+                assert_eq!(diff.rising(), NBV::new(0b0010_0000, 8));
+                assert_eq!(diff.falling(), NBV::new(0b0100_0000, 8));
+                assert!(diff.is_changed());
+                assert!(!diff.before().diff(diff.before()).is_changed());
+                let mut bits = diff.changed_bits();
+                assert_eq!((bits.next(), bits.next(), bits.next()), (Some(5), Some(6), None));
+            }
+
+            #[test]
+            fn [<test_diff_changed_fields_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let fields = [Field::<$type>::new(0, 4), Field::<$type>::new(4, 2), Field::<$type>::new(6, 2)];
+                let diff = NBV::new(0b1100_1010, 8).diff(NBV::new(0b1010_1010, 8));
+                let mut changed = diff.changed_fields(&fields);
+                assert_eq!(changed.next(), Some(&fields[1]));
+                assert_eq!(changed.next(), Some(&fields[2]));
+                assert_eq!(changed.next(), None);
+            }
+
+            #[test]
+            fn [<test_diff_patch_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (before, after) = (NBV::new(0b1100_1010, 8), NBV::new(0b1010_1010, 8));
+                let patch = before.diff(after).patch();
+                assert_eq!(patch, BitPatch::<$type>::new(NBV::new(0b0110_0000, 8), NBV::new(0b1010_0000, 8)));
+                assert_eq!(patch.values(), NBV::new(0b0010_0000, 8));
+                assert_eq!(before.apply(patch), after);
+                assert_eq!(after.apply(patch), after);
+                assert_eq!(NBV::new(0b0100_0001, 8).apply(patch), NBV::new(0b0010_0001, 8));
+                let mut positions = patch.positions();
+                assert_eq!((positions.next(), positions.next(), positions.next()), (Some(5), Some(6), None));
+                assert!(!patch.is_empty());
+                assert!(after.diff(after).patch().is_empty());
+                assert_eq!(NBV::new(0b101, 3).apply(NBV::new(0b101, 3).diff(NBV::new(0b11, 2)).patch()), NBV::new(0b11, 2));
+                assert_eq!(NBV::new(0b1, 1).apply(NBV::new(0b1, 1).diff(NBV::new(0b11, 2)).patch()), NBV::new(0b11, 2));
+                assert_eq!(NBV::new(0b1, 1).diff(NBV::new(0b101, 3)).patch().mask(), NBV::new(0b100, 3));
+            }
+        }
+        };
+    }
+
+    ImplDiffTest!(for u8, u16, u32, u64);
+}
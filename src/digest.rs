@@ -0,0 +1,221 @@
+//! Streaming integrity checks behind a common trait.
+//!
+//! [`BitDigest`] is implemented by the CRC digests of [`crate::crc`], by
+//! [`SelfSyncScrambler`] used as a signature register, and by
+//! [`InternetChecksum`], so framing code can be generic over the check:
+//!
+//! ```
+//! use nanobv::{
+//!     crc::{Crc, CRC_16_XMODEM},
+//!     digest::{BitDigest, InternetChecksum},
+//!     NanoBV,
+//! };
+//!
+//! fn seal<D: BitDigest>(mut digest: D, header: NanoBV<u8>, payload: &[u8]) -> NanoBV<D::Storage> {
+//!     digest.update_bits(header);
+//!     digest.update(payload);
+//!     digest.finalize()
+//! }
+//!
+//! let header = NanoBV::<u8>::new(0x2A, 8);
+//! let crc = seal(Crc::<u16>::new(CRC_16_XMODEM).digest(), header, b"payload");
+//! assert_eq!(crc, Crc::<u16>::new(CRC_16_XMODEM).checksum(b"\x2Apayload"));
+//! let sum = seal(InternetChecksum::new(), header, b"payload");
+//! assert_eq!(sum.len(), 16);
+//! ```
+
+use crate::{
+    backend::{BitVector, BvStorage},
+    crc::Digest,
+    scrambler::SelfSyncScrambler,
+    NanoBV,
+};
+
+/// Integrity check fed one bit or one byte at a time.
+pub trait BitDigest {
+    /// Backing type of the result.
+    type Storage: BvStorage;
+
+    /// Feed a single message bit.
+    fn update_bit(&mut self, bit: bool);
+
+    /// Feed `bytes`, by default most significant bit of each byte first.
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            for offset in (0..8).rev() {
+                self.update_bit((byte >> offset) & 1 == 1);
+            }
+        }
+    }
+
+    /// Feed the bits of `bits`, most significant bit first.
+    fn update_bits<B: BitVector>(&mut self, bits: B) {
+        for offset in (0..bits.len()).rev() {
+            self.update_bit((bits.value().to_u64() >> offset) & 1 == 1);
+        }
+    }
+
+    /// Retrieve the result for the message fed so far.
+    fn finalize(&self) -> NanoBV<Self::Storage>;
+}
+
+/// Internet checksum (RFC 1071): the ones' complement of the ones' complement
+/// sum of big-endian 16-bit words, with a trailing partial word zero padded.
+#[derive(PartialEq, Debug, Eq, Copy, Clone, Default)]
+pub struct InternetChecksum {
+    sum: u32,
+    word: u16,
+    bits: usize,
+}
+
+impl InternetChecksum {
+    /// Create an [`InternetChecksum`] of the empty message.
+    pub const fn new() -> Self {
+        InternetChecksum { sum: 0, word: 0, bits: 0 }
+    }
+
+    /// Fold the carries of `sum` back into its low 16 bits.
+    const fn fold(mut sum: u32) -> u16 {
+        while sum >> 16 != 0 {
+            sum = (sum & 0xFFFF) + (sum >> 16);
+        }
+        sum as u16
+    }
+}
+
+impl BitDigest for InternetChecksum {
+    type Storage = u16;
+
+    fn update_bit(&mut self, bit: bool) {
+        self.word = (self.word << 1) | bit as u16;
+        self.bits += 1;
+        if self.bits == 16 {
+            self.sum = Self::fold(self.sum + self.word as u32) as u32;
+            self.word = 0;
+            self.bits = 0;
+        }
+    }
+
+    fn finalize(&self) -> NanoBV<u16> {
+        let pending = (self.word as u32) << (16 - self.bits) & 0xFFFF;
+        NanoBV::<u16>::new(!Self::fold(self.sum + pending), 16)
+    }
+}
+
+macro_rules! ImplBitDigest {
+    (for $($type:tt),+) => {
+        $(ImplBitDigest!($type);)*
+    };
+
+    ($type:ident) => {
+        /// Bytes are read in the bit order of the CRC algorithm.
+        impl BitDigest for Digest<$type> {
+            type Storage = $type;
+
+            fn update_bit(&mut self, bit: bool) {
+                Digest::<$type>::update_bit(self, bit)
+            }
+
+            fn update(&mut self, bytes: &[u8]) {
+                Digest::<$type>::update(self, bytes)
+            }
+
+            fn finalize(&self) -> NanoBV<$type> {
+                Digest::<$type>::finalize(self)
+            }
+        }
+
+        /// The signature is the shift register state. Bytes are read least
+        /// significant bit first, as in [`SelfSyncScrambler::scramble_bytes`].
+        impl BitDigest for SelfSyncScrambler<$type> {
+            type Storage = $type;
+
+            fn update_bit(&mut self, bit: bool) {
+                self.scramble_bit(bit as $type);
+            }
+
+            fn update(&mut self, bytes: &[u8]) {
+                for byte in bytes {
+                    for offset in 0..8 {
+                        self.scramble_bit((byte >> offset) as $type & 1);
+                    }
+                }
+            }
+
+            fn finalize(&self) -> NanoBV<$type> {
+                self.state()
+            }
+        }
+    };
+}
+
+ImplBitDigest!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crc::{Crc, CRC_16_KERMIT, CRC_32_BZIP2},
+        scrambler::X7_X6_1,
+    };
+
+    fn digest_bits<D: BitDigest>(
+        mut digest: D,
+        bytes: &[u8],
+    ) -> NanoBV<D::Storage> {
+        for &byte in bytes {
+            digest.update_bits(NanoBV::<u8>::new(byte, 8));
+        }
+        digest.finalize()
+    }
+
+    #[test]
+    fn test_digest_crc() {
+        let crc = Crc::<u32>::new(CRC_32_BZIP2);
+        assert_eq!(
+            digest_bits(crc.digest(), b"123456789"),
+            crc.checksum(b"123456789")
+        );
+        let crc = Crc::<u16>::new(CRC_16_KERMIT);
+        let mut digest = crc.digest();
+        BitDigest::update(&mut digest, b"123456789");
+        assert_eq!(BitDigest::finalize(&digest), crc.checksum(b"123456789"));
+        let mut digest = crc.digest();
+        for &byte in b"123456789" {
+            for offset in 0..8 {
+                digest.update_bit((byte >> offset) & 1 == 1);
+            }
+        }
+        assert_eq!(digest.finalize(), crc.checksum(b"123456789"));
+    }
+
+    #[test]
+    fn test_digest_internet_checksum() {
+        // Example from RFC 1071, section 3.
+        let bytes = [0x00, 0x01, 0xF2, 0x03, 0xF4, 0xF5, 0xF6, 0xF7];
+        let mut checksum = InternetChecksum::new();
+        checksum.update(&bytes);
+        assert_eq!(checksum.finalize(), NanoBV::<u16>::new(!0xDDF2, 16));
+        assert_eq!(
+            digest_bits(InternetChecksum::default(), &bytes),
+            checksum.finalize()
+        );
+        let mut odd = InternetChecksum::new();
+        odd.update(&[0x12, 0x34, 0x56]);
+        assert_eq!(odd.finalize(), NanoBV::<u16>::new(!(0x1234 + 0x5600), 16));
+    }
+
+    #[test]
+    fn test_digest_signature_register() {
+        let mut good = SelfSyncScrambler::<u8>::new(X7_X6_1, 0);
+        let mut bad = good;
+        good.update(b"frame");
+        bad.update(b"frane");
+        assert_ne!(good.finalize(), bad.finalize());
+        let mut bits = SelfSyncScrambler::<u8>::new(X7_X6_1, 0);
+        for &byte in b"frame" {
+            bits.update_bits(NanoBV::<u8>::new(byte, 8).reverse());
+        }
+        assert_eq!(bits.finalize(), good.finalize());
+    }
+}
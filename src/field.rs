@@ -0,0 +1,153 @@
+//! Reusable register field descriptors.
+
+use crate::NanoBV;
+use core::marker::PhantomData;
+
+/// Bit field of a register layout, described by its offset and width.
+///
+/// Fields are const-constructible, so a register layout can be declared once
+/// as a set of consts and reused.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Field<T = u32> {
+    offset: usize,
+    width: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Field<T> {
+    /// Retrieve offset of the least significant bit of the field.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Retrieve width of the field.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+}
+
+macro_rules! ImplField {
+    (for $($type:tt),+) => {
+        $(ImplField!($type);)*
+    };
+
+    ($type:ident) => {
+        impl Field<$type> {
+            /// Create a new [`Field`].
+            pub const fn new(offset: usize, width: usize) -> Self {
+                ["Invalid field provided."][((width < 1) || (offset + width > NanoBV::<$type>::BIT_SIZE)) as usize];
+                Field { offset, width, _marker: PhantomData }
+            }
+
+            /// Create a [`Field`] from MSB0 numbering, where `offset` counts from the
+            /// most significant bit of a `length`-bit register.
+            pub const fn from_msb0(offset: usize, width: usize, length: usize) -> Self {
+                ["Invalid field provided."][(offset + width > length) as usize];
+                Field::<$type>::new(length - offset - width, width)
+            }
+
+            /// Retrieve mask of the bits covered by the field.
+            pub const fn mask(&self) -> $type {
+                NanoBV::<$type>::ones(self.width).value() << self.offset
+            }
+
+            /// Read the field from `bv`.
+            pub const fn read(&self, bv: NanoBV<$type>) -> NanoBV<$type> {
+                ["Invalid field provided."][(self.offset + self.width > bv.len()) as usize];
+                NanoBV::<$type>::new(bv.value() >> self.offset, self.width)
+            }
+
+            /// Write `value` into the field of `bv`, truncating it to the width.
+            pub const fn write(&self, bv: NanoBV<$type>, value: $type) -> NanoBV<$type> {
+                ["Invalid field provided."][(self.offset + self.width > bv.len()) as usize];
+                bv.set_value((bv.value() & !self.mask()) | ((value << self.offset) & self.mask()))
+            }
+
+            /// Check whether any bit of the field is set in `bv`.
+            pub const fn is_set(&self, bv: NanoBV<$type>) -> bool {
+                ["Invalid field provided."][(self.offset + self.width > bv.len()) as usize];
+                bv.value() & self.mask() != 0
+            }
+        }
+
+        impl NanoBV<$type> {
+            /// Write several fields in a single masked read-modify-write.
+            ///
+            /// The fields must not overlap.
+            pub const fn apply_fields(&self, fields: &[(Field<$type>, $type)]) -> Self {
+                let (mut mask, mut bits, mut i) = (0, 0, 0);
+                while i < fields.len() {
+                    let (field, value) = fields[i];
+                    ["Invalid field provided."][(field.offset + field.width > self.len()) as usize];
+                    ["Overlapping fields provided."][(mask & field.mask() != 0) as usize];
+                    mask |= field.mask();
+                    bits |= (value << field.offset) & field.mask();
+                    i += 1;
+                }
+                self.set_value((self.data & !mask) | bits)
+            }
+        }
+    };
+}
+
+ImplField!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplFieldTest {
+        (for $($type:tt),+) => {
+            $(ImplFieldTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_field_read_write_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                const FIELD: Field<$type> = Field::<$type>::new(2, 3);
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let written = FIELD.write(bv, 0b101);
+                assert_eq!(FIELD.read(written), NanoBV::<$type>::new(0b101, 3));
+                assert_eq!(written.value() & !FIELD.mask(), bv.value() & !FIELD.mask());
+                assert_eq!(FIELD.write(bv, 0xFF), FIELD.write(bv, 0b111));
+            }
+
+            #[test]
+            fn [<test_field_is_set_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let field = Field::<$type>::new(NBV::BIT_SIZE - 4, 4);
+                assert_eq!(field.mask(), $type::MAX << (NBV::BIT_SIZE - 4));
+                assert!(!field.is_set(NBV::ones(NBV::BIT_SIZE).clear().set_bit(0)));
+                assert!(field.is_set(NBV::zeros(NBV::BIT_SIZE).set_bit((NBV::BIT_SIZE - 1) as $type)));
+            }
+
+            #[test]
+            fn [<test_nanobv_apply_fields_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                const LOW: Field<$type> = Field::<$type>::new(0, 2);
+                const HIGH: Field<$type> = Field::<$type>::new(4, 3);
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let applied = bv.apply_fields(&[(LOW, 0b10), (HIGH, 0b011)]);
+                assert_eq!(applied, HIGH.write(LOW.write(bv, 0b10), 0b011));
+                assert_eq!(bv.apply_fields(&[]), bv);
+            }
+
+            #[test]
+            fn [<test_field_from_msb0_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let field = Field::<$type>::from_msb0(1, 3, 8);
+                assert_eq!(field, Field::<$type>::new(4, 3));
+                assert_eq!(field.read(NBV::new(0b0101_0000, 8)), NBV::new(0b101, 3));
+            }
+        }
+        };
+    }
+
+    ImplFieldTest!(for u8, u16, u32, u64);
+}
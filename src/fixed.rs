@@ -0,0 +1,170 @@
+//! Signed fixed-point Q-format view over NanoBVs.
+//!
+//! A [`Fixed`] reads its bits as a two's complement integer scaled by
+//! `2^-frac`, so a 16-bit value with 12 fractional bits is in Q3.12 format.
+
+use crate::{internals::round_shr, NanoBV, Rounding};
+
+/// Signed fixed-point number with `frac` fractional bits.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Fixed<T = u32> {
+    bits: NanoBV<T>,
+    frac: usize,
+}
+
+impl<T: Copy> Fixed<T> {
+    /// Retrieve the underlying bits.
+    pub const fn bits(&self) -> NanoBV<T> {
+        self.bits
+    }
+
+    /// Retrieve the number of fractional bits.
+    pub const fn frac(&self) -> usize {
+        self.frac
+    }
+}
+
+macro_rules! ImplFixed {
+    (for $($type:tt),+) => {
+        $(ImplFixed!($type);)*
+    };
+
+    ($type:ident) => {
+        impl Fixed<$type> {
+            const fn fits(value: i128, length: usize) -> bool {
+                value >= -(1 << (length - 1)) && value < (1 << (length - 1))
+            }
+
+            /// Create a new [`Fixed`] from its bits and number of fractional bits.
+            pub const fn new(bits: NanoBV<$type>, frac: usize) -> Self {
+                ["Invalid fraction provided."][(frac > bits.len()) as usize];
+                Fixed { bits, frac }
+            }
+
+            /// Create a [`Fixed`] from a scaled integer, i.e. the value multiplied
+            /// by `2^frac`. Returns `None` if it does not fit in `length` bits or
+            /// `frac` exceeds `length`.
+            pub const fn from_scaled(scaled: i64, length: usize, frac: usize) -> Option<Self> {
+                match frac <= length && Self::fits(scaled as i128, length) {
+                true => Some(Fixed::<$type>::new(NanoBV::<$type>::new(scaled as $type, length), frac)),
+                false => None,
+                }
+            }
+
+            /// Create a [`Fixed`] from an integer. Returns `None` if it does not fit
+            /// in `length` bits or `frac` exceeds `length`.
+            pub const fn from_int(value: i64, length: usize, frac: usize) -> Option<Self> {
+                if frac > length {
+                    return None;
+                }
+                let scaled = (value as i128) << frac;
+                match Self::fits(scaled, length) {
+                true => Some(Fixed::<$type>::new(NanoBV::<$type>::new(scaled as $type, length), frac)),
+                false => None,
+                }
+            }
+
+            /// Retrieve the scaled integer, i.e. the value multiplied by `2^frac`.
+            pub const fn to_scaled(&self) -> i64 {
+                self.bits.signed_value()
+            }
+
+            /// Convert to an integer, rounding the fractional bits.
+            pub const fn to_int(&self, rounding: Rounding) -> i64 {
+                round_shr(self.to_scaled() as i128, self.frac, rounding) as i64
+            }
+
+            /// Change the number of fractional bits while retaining length,
+            /// rounding discarded bits. Returns `None` if the result does not fit.
+            pub const fn rescale(&self, frac: usize, rounding: Rounding) -> Option<Self> {
+                if frac > self.bits.len() {
+                    return None;
+                }
+                let scaled = match frac < self.frac {
+                true => round_shr(self.to_scaled() as i128, self.frac - frac, rounding),
+                false => (self.to_scaled() as i128) << (frac - self.frac),
+                };
+                match Self::fits(scaled, self.bits.len()) {
+                true => Some(Fixed::<$type>::new(self.bits.set_value(scaled as $type), frac)),
+                false => None,
+                }
+            }
+
+            /// Add `rhs`, which must have the same number of fractional bits,
+            /// wrapping within the shorter length.
+            pub const fn add(&self, rhs: Self) -> Self {
+                ["Invalid fraction provided."][(self.frac != rhs.frac) as usize];
+                let length = $crate::internals::min(self.bits.len(), rhs.bits.len());
+                Fixed::<$type>::new(NanoBV::<$type>::new(self.bits.value().wrapping_add(rhs.bits.value()), length), self.frac)
+            }
+
+            /// Subtract `rhs`, which must have the same number of fractional bits,
+            /// wrapping within the shorter length.
+            pub const fn sub(&self, rhs: Self) -> Self {
+                ["Invalid fraction provided."][(self.frac != rhs.frac) as usize];
+                let length = $crate::internals::min(self.bits.len(), rhs.bits.len());
+                Fixed::<$type>::new(NanoBV::<$type>::new(self.bits.value().wrapping_sub(rhs.bits.value()), length), self.frac)
+            }
+
+            /// Multiply by `rhs` exactly. The result has the sum of the lengths and
+            /// of the fractional bits, which must fit in the backing type.
+            pub const fn mul(&self, rhs: Self) -> Self {
+                let product = self.to_scaled() as i128 * rhs.to_scaled() as i128;
+                let length = self.bits.len() + rhs.bits.len();
+                ["Invalid length provided."][(length > NanoBV::<$type>::BIT_SIZE) as usize];
+                Fixed::<$type>::new(NanoBV::<$type>::new(product as $type, length), self.frac + rhs.frac)
+            }
+        }
+    };
+}
+
+ImplFixed!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplFixedTest {
+        (for $($type:tt),+) => {
+            $(ImplFixedTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_fixed_conversions_ $type>]() {
+                type Q = Fixed::<$type>;
+                let value = Q::from_scaled(-6, 8, 2).unwrap();
+                assert_eq!(value.bits(), NanoBV::<$type>::new(0b1111_1010, 8));
+                assert_eq!((value.to_scaled(), value.frac()), (-6, 2));
+                assert_eq!(value.to_int(Rounding::Truncate), -2);
+                assert_eq!(value.to_int(Rounding::Nearest), -1);
+                assert_eq!(value.to_int(Rounding::NearestEven), -2);
+                assert_eq!(Q::from_int(-3, 8, 4), Q::from_scaled(-48, 8, 4));
+                assert_eq!(Q::from_int(8, 8, 4), None);
+                assert_eq!(Q::from_scaled(128, 8, 0), None);
+                assert_eq!(Q::from_scaled(1, 8, 9), None);
+                assert_eq!(Q::from_int(0, 8, 9), None);
+                assert_eq!(Q::from_int(0, 8, 100), None);
+                assert_eq!(value.rescale(4, Rounding::Truncate), Q::from_scaled(-24, 8, 4));
+                assert_eq!(value.rescale(1, Rounding::Nearest), Q::from_scaled(-3, 8, 1));
+                assert_eq!(Q::from_int(5, 8, 2).unwrap().rescale(5, Rounding::Truncate), None);
+            }
+
+            #[test]
+            fn [<test_fixed_arithmetic_ $type>]() {
+                type Q = Fixed::<$type>;
+                let (a, b) = (Q::from_scaled(6, 4, 1).unwrap(), Q::from_scaled(-3, 4, 1).unwrap());
+                assert_eq!(a.add(b), Q::from_scaled(3, 4, 1).unwrap());
+                assert_eq!(b.sub(a), Q::from_scaled(7, 4, 1).unwrap());
+                assert_eq!(a.sub(b), Q::from_scaled(-7, 4, 1).unwrap());
+                assert_eq!(a.mul(b), Q::from_scaled(-18, 8, 2).unwrap());
+                assert_eq!(a.mul(b).to_int(Rounding::Nearest), -4);
+            }
+        }
+        };
+    }
+
+    ImplFixedTest!(for u8, u16, u32, u64);
+}
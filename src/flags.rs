@@ -0,0 +1,114 @@
+//! Arithmetic with NZCV condition flags.
+//!
+//! Flags follow the ARM conventions and are computed for the declared length
+//! rather than the size of the backing type. For subtraction, the carry flag
+//! is set when no borrow occurs.
+
+use crate::NanoBV;
+
+/// Negative, zero, carry and overflow condition flags.
+#[derive(PartialEq, Debug, Eq, Copy, Clone, Default)]
+pub struct Flags {
+    /// Most significant bit of the result is set.
+    pub n: bool,
+    /// Result is zero.
+    pub z: bool,
+    /// Unsigned carry out of the most significant bit.
+    pub c: bool,
+    /// Signed overflow.
+    pub v: bool,
+}
+
+macro_rules! ImplNanoBVFlags {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVFlags!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            const fn flags_add(lhs: $type, rhs: $type, carry: bool, length: usize) -> (Self, Flags) {
+                let sum = lhs as u128 + rhs as u128 + carry as u128;
+                let result = NanoBV::<$type>::new(sum as $type, length);
+                let msb = length - 1;
+                let flags = Flags {
+                    n: (result.data >> msb) & 1 == 1,
+                    z: result.data == 0,
+                    c: (sum >> length) & 1 == 1,
+                    v: ((lhs ^ result.data) & (rhs ^ result.data)) >> msb & 1 == 1,
+                };
+                (result, flags)
+            }
+
+            /// Add `rhs`, computing the flags for the shorter length.
+            pub const fn add_with_flags(&self, rhs: Self) -> (Self, Flags) {
+                self.adc_with_flags(rhs, false)
+            }
+
+            /// Add `rhs` and `carry`, computing the flags for the shorter length.
+            pub const fn adc_with_flags(&self, rhs: Self, carry: bool) -> (Self, Flags) {
+                let length = $crate::internals::min(self.len(), rhs.len());
+                let mask = NanoBV::<$type>::ones(length).data;
+                Self::flags_add(self.data & mask, rhs.data & mask, carry, length)
+            }
+
+            /// Subtract `rhs`, computing the flags for the shorter length.
+            pub const fn sub_with_flags(&self, rhs: Self) -> (Self, Flags) {
+                self.sbc_with_flags(rhs, true)
+            }
+
+            /// Subtract `rhs` and the borrow `!carry`, computing the flags for the
+            /// shorter length.
+            pub const fn sbc_with_flags(&self, rhs: Self, carry: bool) -> (Self, Flags) {
+                let length = $crate::internals::min(self.len(), rhs.len());
+                let mask = NanoBV::<$type>::ones(length).data;
+                Self::flags_add(self.data & mask, !rhs.data & mask, carry, length)
+            }
+        }
+    };
+}
+
+ImplNanoBVFlags!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    const fn flags(n: bool, z: bool, c: bool, v: bool) -> Flags {
+        Flags { n, z, c, v }
+    }
+
+    macro_rules! ImplNanoBVFlagsTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVFlagsTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_add_with_flags_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0x7F, 8).add_with_flags(NBV::new(1, 8)), (NBV::new(0x80, 8), flags(true, false, false, true)));
+                assert_eq!(NBV::new(0xFF, 8).add_with_flags(NBV::new(1, 8)), (NBV::new(0, 8), flags(false, true, true, false)));
+                assert_eq!(NBV::new(0x80, 8).add_with_flags(NBV::new(0x80, 8)), (NBV::new(0, 8), flags(false, true, true, true)));
+                assert_eq!(NBV::new(0x3, 4).adc_with_flags(NBV::new(0x4, 4), true), (NBV::new(0x8, 4), flags(true, false, false, true)));
+                let max = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(max.add_with_flags(max), (NBV::new($type::MAX - 1, NBV::BIT_SIZE), flags(true, false, true, false)));
+            }
+
+            #[test]
+            fn [<test_nanobv_sub_with_flags_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(5, 8).sub_with_flags(NBV::new(5, 8)), (NBV::new(0, 8), flags(false, true, true, false)));
+                assert_eq!(NBV::new(0, 8).sub_with_flags(NBV::new(1, 8)), (NBV::new(0xFF, 8), flags(true, false, false, false)));
+                assert_eq!(NBV::new(0x80, 8).sub_with_flags(NBV::new(1, 8)), (NBV::new(0x7F, 8), flags(false, false, true, true)));
+                assert_eq!(NBV::new(5, 4).sbc_with_flags(NBV::new(2, 4), false), (NBV::new(2, 4), flags(false, false, true, false)));
+                let min = NBV::new(1 << (NBV::BIT_SIZE - 1), NBV::BIT_SIZE);
+                assert_eq!(min.sub_with_flags(NBV::new(1, NBV::BIT_SIZE)), (NBV::new(min.value() - 1, NBV::BIT_SIZE), flags(false, false, true, true)));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVFlagsTest!(for u8, u16, u32, u64);
+}
@@ -0,0 +1,96 @@
+//! IEEE-754 bit-pattern fields.
+//!
+//! Single precision values are handled as `NanoBV<u32>` and double precision
+//! values as `NanoBV<u64>`, both with the full length of the backing type.
+
+use crate::{field::Field, NanoBV};
+
+/// Sign bit of an `f32`.
+pub const F32_SIGN: Field<u32> = Field::<u32>::new(31, 1);
+/// Biased exponent of an `f32`.
+pub const F32_EXPONENT: Field<u32> = Field::<u32>::new(23, 8);
+/// Mantissa of an `f32`, without the implicit leading bit.
+pub const F32_MANTISSA: Field<u32> = Field::<u32>::new(0, 23);
+
+/// Sign bit of an `f64`.
+pub const F64_SIGN: Field<u64> = Field::<u64>::new(63, 1);
+/// Biased exponent of an `f64`.
+pub const F64_EXPONENT: Field<u64> = Field::<u64>::new(52, 11);
+/// Mantissa of an `f64`, without the implicit leading bit.
+pub const F64_MANTISSA: Field<u64> = Field::<u64>::new(0, 52);
+
+macro_rules! ImplNanoBVFloat {
+    (for $(($type:ident, $float:ident, $from_bits:ident, $to_bits:ident, $sign:ident, $exponent:ident, $mantissa:ident)),+) => {
+        $(impl NanoBV<$type> {
+            #[doc = concat!("Create a [`NanoBV`] holding the bit pattern of an `", stringify!($float), "`.")]
+            pub fn $from_bits(value: $float) -> Self {
+                NanoBV::<$type>::new(value.to_bits(), Self::BIT_SIZE)
+            }
+
+            #[doc = concat!("Interpret the current NanoBV as the bit pattern of an `", stringify!($float), "`.")]
+            pub fn $to_bits(&self) -> $float {
+                ["Invalid length provided."][(self.len() != Self::BIT_SIZE) as usize];
+                $float::from_bits(self.data)
+            }
+
+            /// Assemble a floating-point bit pattern from its sign, biased exponent
+            /// and mantissa fields.
+            pub const fn from_float_parts(sign: Self, exponent: Self, mantissa: Self) -> Self {
+                ["Invalid length provided."][((sign.len() != $sign.width()) || (exponent.len() != $exponent.width()) || (mantissa.len() != $mantissa.width())) as usize];
+                NanoBV::<$type>::default().apply_fields(&[($sign, sign.data), ($exponent, exponent.data), ($mantissa, mantissa.data)])
+            }
+
+            /// Retrieve the sign bit of a floating-point bit pattern.
+            pub const fn float_sign(&self) -> Self {
+                $sign.read(*self)
+            }
+
+            /// Retrieve the biased exponent of a floating-point bit pattern.
+            pub const fn float_exponent(&self) -> Self {
+                $exponent.read(*self)
+            }
+
+            /// Retrieve the mantissa of a floating-point bit pattern, without the
+            /// implicit leading bit.
+            pub const fn float_mantissa(&self) -> Self {
+                $mantissa.read(*self)
+            }
+        })*
+    };
+}
+
+ImplNanoBVFloat!(for (u32, f32, from_f32_bits, to_f32_bits, F32_SIGN, F32_EXPONENT, F32_MANTISSA), (u64, f64, from_f64_bits, to_f64_bits, F64_SIGN, F64_EXPONENT, F64_MANTISSA));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nanobv_f32_fields() {
+        let bv = NanoBV::<u32>::from_f32_bits(-1.5);
+        assert_eq!(bv, NanoBV::<u32>::new(0xBFC0_0000, 32));
+        assert_eq!(bv.float_sign(), NanoBV::<u32>::new(1, 1));
+        assert_eq!(bv.float_exponent(), NanoBV::<u32>::new(127, 8));
+        assert_eq!(bv.float_mantissa(), NanoBV::<u32>::new(0x40_0000, 23));
+        let parts = NanoBV::<u32>::from_float_parts(
+            bv.float_sign(),
+            bv.float_exponent(),
+            bv.float_mantissa(),
+        );
+        assert_eq!(parts.to_f32_bits(), -1.5);
+    }
+
+    #[test]
+    fn test_nanobv_f64_fields() {
+        let bv = NanoBV::<u64>::from_f64_bits(0.25);
+        assert_eq!(bv.float_sign(), NanoBV::<u64>::new(0, 1));
+        assert_eq!(bv.float_exponent(), NanoBV::<u64>::new(1021, 11));
+        assert_eq!(bv.float_mantissa(), NanoBV::<u64>::zeros(52));
+        let infinity = NanoBV::<u64>::from_float_parts(
+            NanoBV::<u64>::new(1, 1),
+            NanoBV::<u64>::ones(11),
+            NanoBV::<u64>::zeros(52),
+        );
+        assert_eq!(infinity.to_f64_bits(), f64::NEG_INFINITY);
+    }
+}
@@ -0,0 +1,178 @@
+//! Polynomial arithmetic over GF(2).
+//!
+//! Bit `i` of a [`NanoBV`] holds the coefficient of `x^i`, so addition is
+//! [`NanoBV::bvxor`] and the operations below provide the rest of the ring.
+
+use crate::NanoBV;
+
+macro_rules! ImplNanoBVGf2 {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVGf2!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            const fn gf2_degree(value: $type) -> usize {
+                Self::BIT_SIZE - 1 - value.leading_zeros() as usize
+            }
+
+            const fn gf2_mul_truncated(lhs: $type, rhs: $type) -> $type {
+                let (mut lhs, mut rhs, mut product) = (lhs, rhs, 0);
+                while rhs != 0 {
+                    product ^= lhs & (rhs & 1).wrapping_neg();
+                    lhs <<= 1;
+                    rhs >>= 1;
+                }
+                product
+            }
+
+            /// Carry-less multiplication, with a length of `self.len() + rhs.len() - 1`.
+            pub const fn gf2_mul(&self, rhs: Self) -> Self {
+                let length = self.len() + rhs.len() - 1;
+                ["Invalid length provided."][(length > Self::BIT_SIZE) as usize];
+                NanoBV::<$type>::new(Self::gf2_mul_truncated(self.data, rhs.data), length)
+            }
+
+            /// Polynomial division, returning the quotient and the remainder.
+            pub const fn gf2_divrem(&self, divisor: Self) -> (Self, Self) {
+                ["Invalid divisor provided."][(divisor.data == 0) as usize];
+                let divisor_degree = Self::gf2_degree(divisor.data);
+                let (mut quotient, mut remainder) = (0, self.data);
+                while remainder != 0 && Self::gf2_degree(remainder) >= divisor_degree {
+                    let shift = Self::gf2_degree(remainder) - divisor_degree;
+                    quotient |= 1 << shift;
+                    remainder ^= divisor.data << shift;
+                }
+                (NanoBV::<$type>::new(quotient, self.len()), NanoBV::<$type>::new(remainder, divisor.len()))
+            }
+
+            /// Polynomial reduction modulo `modulus`.
+            pub const fn gf2_rem(&self, modulus: Self) -> Self {
+                self.gf2_divrem(modulus).1
+            }
+
+            /// Polynomial multiplication modulo `modulus`.
+            pub const fn gf2_mulmod(&self, rhs: Self, modulus: Self) -> Self {
+                let degree = Self::gf2_degree(modulus.data);
+                let mut lhs = self.gf2_rem(modulus).data;
+                let mut rhs = rhs.gf2_rem(modulus).data;
+                let mut product = 0;
+                while rhs != 0 {
+                    product ^= lhs & (rhs & 1).wrapping_neg();
+                    rhs >>= 1;
+                    lhs <<= 1;
+                    lhs ^= modulus.data & ((lhs >> degree) & 1).wrapping_neg();
+                }
+                NanoBV::<$type>::new(product, modulus.len())
+            }
+
+            /// Greatest common divisor of two polynomials, both truncated to the
+            /// shorter length.
+            pub const fn gf2_gcd(&self, rhs: Self) -> Self {
+                let length = $crate::internals::min(self.len(), rhs.len());
+                let (mut a, mut b) = (NanoBV::<$type>::new(self.data, length), NanoBV::<$type>::new(rhs.data, length));
+                while b.data != 0 {
+                    let remainder = a.gf2_rem(b);
+                    a = b;
+                    b = remainder;
+                }
+                NanoBV::<$type>::new(a.data, length)
+            }
+
+            /// Multiplicative inverse modulo `modulus`, if one exists.
+            pub const fn gf2_inverse(&self, modulus: Self) -> Option<Self> {
+                let (mut r0, mut r1) = (modulus.data, self.gf2_rem(modulus).data);
+                let (mut s0, mut s1) = (0, 1);
+                while r1 != 0 {
+                    let (quotient, remainder) = NanoBV::<$type>::new(r0, Self::BIT_SIZE)
+                        .gf2_divrem(NanoBV::<$type>::new(r1, Self::BIT_SIZE));
+                    r0 = r1;
+                    r1 = remainder.data;
+                    let s = s0 ^ Self::gf2_mul_truncated(quotient.data, s1);
+                    s0 = s1;
+                    s1 = s;
+                }
+                match r0 {
+                1 => Some(NanoBV::<$type>::new(s0, modulus.len()).gf2_rem(modulus)),
+                _ => None,
+                }
+            }
+        }
+    };
+}
+
+ImplNanoBVGf2!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVGf2Test {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVGf2Test!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_gf2_divrem_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let dividend = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let divisor = NBV::new(rng.generate() | 1, NBV::BIT_SIZE / 2);
+                let (quotient, remainder) = dividend.gf2_divrem(divisor);
+                let product = NBV::new(NBV::gf2_mul_truncated(quotient.value(), divisor.value()), NBV::BIT_SIZE);
+                assert_eq!(product.bvxor(NBV::new(remainder.value(), NBV::BIT_SIZE)), dividend);
+                assert!(remainder.value() < divisor.value() || remainder.value() == 0);
+            }
+
+            #[test]
+            fn [<test_nanobv_gf2_inverse_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let modulus = NBV::new(0b111, 3);
+                let inverse = NBV::new(0b10, 3).gf2_inverse(modulus).unwrap();
+                assert_eq!(inverse.value(), 0b11);
+                assert_eq!(NBV::new(0b10, 3).gf2_mulmod(inverse, modulus).value(), 1);
+                assert_eq!(NBV::new(0b11, 3).gf2_inverse(NBV::new(0b101, 3)), None);
+            }
+        }
+        };
+    }
+
+    ImplNanoBVGf2Test!(for u8, u16, u32, u64);
+
+    #[test]
+    fn test_nanobv_gf2_mul() {
+        let bv = NanoBV::<u8>::new(0b11, 2);
+        assert_eq!(bv.gf2_mul(bv), NanoBV::<u8>::new(0b101, 3));
+        assert_eq!(
+            NanoBV::<u16>::new(0xFF, 8).gf2_mul(NanoBV::<u16>::new(0xFF, 8)),
+            NanoBV::<u16>::new(0x5555, 15)
+        );
+    }
+
+    #[test]
+    fn test_nanobv_gf2_aes_field() {
+        let modulus = NanoBV::<u16>::new(0x11B, 9);
+        let a = NanoBV::<u16>::new(0x57, 8);
+        let b = NanoBV::<u16>::new(0x83, 8);
+        assert_eq!(a.gf2_mulmod(b, modulus).value(), 0xC1);
+        let inverse = NanoBV::<u16>::new(0x53, 8).gf2_inverse(modulus).unwrap();
+        assert_eq!(inverse.value(), 0xCA);
+    }
+
+    #[test]
+    fn test_nanobv_gf2_gcd() {
+        // (x + 1)(x^2 + x + 1) and (x + 1)^2 share the factor x + 1.
+        let a = NanoBV::<u8>::new(0b1001, 4);
+        let b = NanoBV::<u8>::new(0b101, 4);
+        assert_eq!(a.gf2_gcd(b), NanoBV::<u8>::new(0b11, 4));
+        // A zero divisor leaves self, truncated to the shorter length.
+        let a = NanoBV::<u8>::new(0b1011, 4);
+        let gcd = a.gf2_gcd(NanoBV::<u8>::zeros(2));
+        assert_eq!(gcd, NanoBV::<u8>::new(0b11, 2));
+        assert_eq!(NanoBV::<u8>::new(a.value(), 2).gf2_rem(gcd).value(), 0);
+    }
+}
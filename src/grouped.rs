@@ -0,0 +1,138 @@
+//! Digit-grouped formatting.
+//!
+//! ```
+//! use nanobv::{verilog::Radix, NanoBV};
+//!
+//! let bv = NanoBV::<u16>::new(0x2BAC, 14);
+//! assert_eq!(format!("{}", bv.display_grouped(Radix::Binary, 4)), "10_1011_1010_1100");
+//! assert_eq!(format!("{}", bv.display_grouped(Radix::Hex, 2).separator(' ')), "2b ac");
+//! ```
+
+use crate::{
+    internals::{write_decimal, write_digits},
+    verilog::Radix,
+    NanoBV,
+};
+use core::fmt;
+
+/// [`fmt::Display`] adapter writing the digits of a NanoBV in groups of
+/// `group` digits, counted from the least significant digit.
+///
+/// Binary, octal and hexadecimal values are written with all
+/// `ceil(len / bits per digit)` digits, and decimal values without leading
+/// zeros. The default separator is `_`.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Grouped {
+    value: u64,
+    length: usize,
+    radix: Radix,
+    group: usize,
+    separator: char,
+}
+
+impl Grouped {
+    /// Replace the separator written between groups.
+    pub const fn separator(self, separator: char) -> Self {
+        Grouped { separator, ..self }
+    }
+}
+
+impl fmt::Display for Grouped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0; 64];
+        let count = match self.radix {
+            Radix::Binary => write_digits(self.value, self.length, 1, &mut buf),
+            Radix::Octal => {
+                write_digits(self.value, self.length.div_ceil(3), 3, &mut buf)
+            }
+            Radix::Decimal => write_decimal(self.value, &mut buf),
+            Radix::Hex => {
+                write_digits(self.value, self.length.div_ceil(4), 4, &mut buf)
+            }
+        }
+        .map_err(|_| fmt::Error)?;
+        for (i, &digit) in buf[..count].iter().enumerate() {
+            if i != 0 && (count - i) % self.group == 0 {
+                fmt::Write::write_char(f, self.separator)?;
+            }
+            fmt::Write::write_char(f, digit as char)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! ImplNanoBVGrouped {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVGrouped!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Format the current NanoBV in `radix` with digits in groups of
+            /// `group`, as in `1010_1100`.
+            pub const fn display_grouped(&self, radix: Radix, group: usize) -> Grouped {
+                ["Invalid group provided."][(group == 0) as usize];
+                Grouped { value: self.data as u64, length: self.len(), radix, group, separator: '_' }
+            }
+        }
+    };
+}
+
+ImplNanoBVGrouped!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    struct Buffer {
+        bytes: [u8; 128],
+        len: usize,
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.bytes[self.len..self.len + s.len()]
+                .copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            Ok(())
+        }
+    }
+
+    fn render(grouped: Grouped) -> Buffer {
+        let mut buffer = Buffer { bytes: [0; 128], len: 0 };
+        write!(buffer, "{}", grouped).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_nanobv_display_grouped() {
+        let bv = NanoBV::<u8>::new(0b1010_1100, 8);
+        assert_eq!(
+            &render(bv.display_grouped(Radix::Binary, 4)).bytes[..9],
+            b"1010_1100"
+        );
+        assert_eq!(
+            &render(bv.display_grouped(Radix::Binary, 3)).bytes[..10],
+            b"10_101_100"
+        );
+        assert_eq!(
+            &render(bv.display_grouped(Radix::Octal, 2)).bytes[..4],
+            b"2_54"
+        );
+        assert_eq!(
+            &render(bv.display_grouped(Radix::Hex, 8)).bytes[..2],
+            b"ac"
+        );
+        let bv = NanoBV::<u32>::new(1_234_567, 32);
+        assert_eq!(
+            &render(bv.display_grouped(Radix::Decimal, 3).separator(',')).bytes
+                [..9],
+            b"1,234,567"
+        );
+        let bv = NanoBV::<u64>::ones(64);
+        let buffer = render(bv.display_grouped(Radix::Hex, 4).separator(' '));
+        assert_eq!(&buffer.bytes[..buffer.len], b"ffff ffff ffff ffff");
+        assert_eq!(render(bv.display_grouped(Radix::Binary, 8)).len, 71);
+    }
+}
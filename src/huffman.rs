@@ -0,0 +1,200 @@
+//! Canonical Huffman codes built from a code-length table.
+//!
+//! As in DEFLATE (RFC 1951, section 3.2.2), shorter codes precede longer
+//! ones, and codes of the same length are assigned to symbols in increasing
+//! order. A length of zero marks an unused symbol. Codes are written most
+//! significant bit first through the [`bitstream`](crate::bitstream) layer:
+//!
+//! ```
+//! use nanobv::{
+//!     bitstream::{BitReader, BitWriter},
+//!     huffman::CanonicalHuffman,
+//!     NanoBV,
+//! };
+//!
+//! // Example from RFC 1951: A=010, B=011, C=100, D=101, E=110, F=00, G=1110, H=1111.
+//! let huffman = CanonicalHuffman::new(&[3, 3, 3, 3, 3, 2, 4, 4], 15).unwrap();
+//! assert_eq!(huffman.code(6), Some(NanoBV::<u16>::new(0b1110, 4)));
+//!
+//! let mut buf = [0; 1];
+//! let mut writer = BitWriter::new(&mut buf);
+//! huffman.encode(5, &mut writer).unwrap();
+//! huffman.encode(7, &mut writer).unwrap();
+//! assert_eq!(writer.finish(), 1);
+//!
+//! let mut reader = BitReader::new(&buf);
+//! assert_eq!(huffman.decode(&mut reader), Ok(5));
+//! assert_eq!(huffman.decode(&mut reader), Ok(7));
+//! ```
+
+use crate::{
+    bitstream::{BitReader, BitWriter},
+    Error, NanoBV,
+};
+
+/// Longest code length supported, as in DEFLATE.
+pub const MAX_CODE_LENGTH: usize = 15;
+
+/// Canonical Huffman code over a borrowed code-length table.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct CanonicalHuffman<'a> {
+    lengths: &'a [u8],
+    counts: [u16; MAX_CODE_LENGTH + 1],
+    first: [u16; MAX_CODE_LENGTH + 1],
+}
+
+impl<'a> CanonicalHuffman<'a> {
+    /// Build the code assigning `lengths[symbol]` bits to each symbol.
+    ///
+    /// Returns [`Error::InvalidLength`] if a length exceeds `max_length` or
+    /// [`MAX_CODE_LENGTH`], and [`Error::InvalidFormat`] if the lengths
+    /// describe more codes than fit. Incomplete codes are accepted.
+    pub const fn new(
+        lengths: &'a [u8],
+        max_length: usize,
+    ) -> Result<Self, Error> {
+        if max_length > MAX_CODE_LENGTH {
+            return Err(Error::InvalidLength);
+        }
+        let mut counts = [0u16; MAX_CODE_LENGTH + 1];
+        let mut i = 0;
+        while i < lengths.len() {
+            if lengths[i] as usize > max_length {
+                return Err(Error::InvalidLength);
+            }
+            counts[lengths[i] as usize] += 1;
+            i += 1;
+        }
+        counts[0] = 0;
+        let (mut first, mut code, mut length) =
+            ([0u16; MAX_CODE_LENGTH + 1], 0u32, 1);
+        while length <= MAX_CODE_LENGTH {
+            code = (code + counts[length - 1] as u32) << 1;
+            first[length] = code as u16;
+            if code + counts[length] as u32 > 1 << length {
+                return Err(Error::InvalidFormat);
+            }
+            length += 1;
+        }
+        Ok(CanonicalHuffman { lengths, counts, first })
+    }
+
+    /// Retrieve the code of `symbol`, or `None` if it is unused or out of
+    /// range.
+    pub const fn code(&self, symbol: usize) -> Option<NanoBV<u16>> {
+        if symbol >= self.lengths.len() || self.lengths[symbol] == 0 {
+            return None;
+        }
+        let length = self.lengths[symbol];
+        let (mut code, mut i) = (self.first[length as usize], 0);
+        while i < symbol {
+            code += (self.lengths[i] == length) as u16;
+            i += 1;
+        }
+        Some(NanoBV::<u16>::new(code, length as usize))
+    }
+
+    /// Write the code of `symbol`, returning [`Error::InvalidFormat`] if it is
+    /// unused or out of range.
+    pub fn encode(
+        &self,
+        symbol: usize,
+        writer: &mut BitWriter,
+    ) -> Result<(), Error> {
+        writer.write(self.code(symbol).ok_or(Error::InvalidFormat)?)
+    }
+
+    /// Read one code and return its symbol, or [`Error::InvalidFormat`] if the
+    /// bits match no code.
+    pub fn decode(&self, reader: &mut BitReader) -> Result<usize, Error> {
+        let mut code = 0;
+        for length in 1..=MAX_CODE_LENGTH {
+            code = (code << 1) | reader.read_bit()? as u16;
+            let index = code.wrapping_sub(self.first[length]);
+            if index < self.counts[length] {
+                return self
+                    .symbol(length as u8, index as usize)
+                    .ok_or(Error::InvalidFormat);
+            }
+        }
+        Err(Error::InvalidFormat)
+    }
+
+    /// Retrieve the `index`-th symbol with a code of `length` bits.
+    fn symbol(&self, length: u8, index: usize) -> Option<usize> {
+        self.lengths
+            .iter()
+            .enumerate()
+            .filter(|(_, &l)| l == length)
+            .nth(index)
+            .map(|(symbol, _)| symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_huffman_codes() {
+        let lengths = [3, 3, 3, 3, 3, 2, 4, 4];
+        let huffman = CanonicalHuffman::new(&lengths, 4).unwrap();
+        let expected =
+            [0b010, 0b011, 0b100, 0b101, 0b110, 0b00, 0b1110, 0b1111];
+        for (symbol, (&code, &length)) in
+            expected.iter().zip(lengths.iter()).enumerate()
+        {
+            assert_eq!(
+                huffman.code(symbol),
+                Some(NanoBV::<u16>::new(code, length as usize))
+            );
+        }
+        assert_eq!(huffman.code(8), None);
+        let sparse = CanonicalHuffman::new(&[0, 1, 0, 2], 15).unwrap();
+        assert_eq!(sparse.code(0), None);
+        assert_eq!(sparse.code(1), Some(NanoBV::<u16>::new(0b0, 1)));
+        assert_eq!(sparse.code(3), Some(NanoBV::<u16>::new(0b10, 2)));
+    }
+
+    #[test]
+    fn test_huffman_validation() {
+        assert_eq!(
+            CanonicalHuffman::new(&[3, 3, 5], 4),
+            Err(Error::InvalidLength)
+        );
+        assert_eq!(CanonicalHuffman::new(&[1], 16), Err(Error::InvalidLength));
+        assert_eq!(
+            CanonicalHuffman::new(&[1, 1, 1], 15),
+            Err(Error::InvalidFormat)
+        );
+        assert_eq!(
+            CanonicalHuffman::new(&[2, 2, 2, 2, 3], 15),
+            Err(Error::InvalidFormat)
+        );
+        assert!(CanonicalHuffman::new(&[2, 2, 2], 15).is_ok());
+        assert!(CanonicalHuffman::new(&[1; 2], 1).is_ok());
+    }
+
+    #[test]
+    fn test_huffman_roundtrip() {
+        let lengths = [2, 1, 3, 0, 3];
+        let huffman = CanonicalHuffman::new(&lengths, 15).unwrap();
+        let message = [1, 0, 4, 2, 1, 1, 4];
+        let mut buf = [0; 2];
+        let mut writer = BitWriter::new(&mut buf);
+        for &symbol in &message {
+            huffman.encode(symbol, &mut writer).unwrap();
+        }
+        assert_eq!(huffman.encode(3, &mut writer), Err(Error::InvalidFormat));
+        assert_eq!(writer.finish(), 2);
+        let mut reader = BitReader::new(&buf);
+        for &symbol in &message {
+            assert_eq!(huffman.decode(&mut reader), Ok(symbol));
+        }
+        let incomplete = CanonicalHuffman::new(&[2, 2, 2], 15).unwrap();
+        assert_eq!(
+            incomplete.decode(&mut BitReader::new(&[0xC0])),
+            Err(Error::InvalidFormat)
+        );
+    }
+}
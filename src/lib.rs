@@ -1,41 +1,228 @@
 #![no_std]
+#![cfg_attr(feature = "const_trait_impl", feature(const_trait_impl, const_ops))]
+#![allow(clippy::upper_case_acronyms)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "div")]
+use core::ops::{Div, DivAssign, Rem, RemAssign};
 use core::{
-    convert::{TryFrom, TryInto},
+    cmp::Ordering,
+    fmt,
     mem::size_of,
-    num::NonZeroUsize,
-    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub},
+    num::NonZeroU8,
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor,
+        BitXorAssign, Mul, MulAssign, Shl, ShlAssign, Shr, ShrAssign, Sub,
+        SubAssign,
+    },
 };
 
+pub mod backend;
+pub mod bcd;
+pub mod bitboard;
+pub mod bitflags;
+pub mod bitset;
+#[cfg(feature = "coding")]
+pub mod bitstream;
+pub mod bloom;
+pub mod builder;
+pub mod can;
+pub mod checked;
+pub mod color;
+#[cfg(feature = "coding")]
+pub mod crc;
+pub mod decode;
+#[cfg(all(feature = "alloc", feature = "fmt"))]
+pub mod diagram;
+pub mod diff;
+#[cfg(feature = "coding")]
+pub mod digest;
+pub mod field;
+pub mod fixed;
+pub mod flags;
+pub mod float;
+pub mod gf2;
+#[cfg(feature = "fmt")]
+pub mod grouped;
+#[cfg(feature = "coding")]
+pub mod huffman;
+#[cfg(feature = "coding")]
+pub mod line;
+pub mod matrix;
+#[cfg(feature = "num-traits")]
+pub mod num;
+pub mod pattern;
+#[cfg(kani)]
+mod proofs;
+#[cfg(feature = "picorand")]
+pub mod random;
+#[cfg(feature = "coding")]
+pub mod regroup;
+pub mod riscv;
+#[cfg(feature = "coding")]
+pub mod scrambler;
+pub mod segment;
+pub mod shifter;
+pub mod slice;
+pub mod smt;
+#[cfg(feature = "svd2rust")]
+pub mod svd;
+pub mod swar;
+pub mod swizzle;
+pub mod symbols;
+#[cfg(feature = "coding")]
+pub mod uart;
+#[cfg(feature = "fmt")]
+pub mod verilog;
+#[cfg(feature = "fmt")]
+pub mod vhdl;
+pub mod wire;
+
+/// Bitvector with a declared length, backed by an unsigned integer.
+///
+/// The length is stored as a [`NonZeroU8`], so `Option<NanoBV<T>>` is
+/// guaranteed to be the same size as `NanoBV<T>`.
+///
+/// Binary operations keep the shorter of the two lengths. Operands with
+/// different backing types are first widened to the larger one.
 #[derive(PartialEq, Debug, Eq, Copy, Clone)]
 pub struct NanoBV<T = u32> {
     data: T,
-    length: NonZeroUsize,
+    length: NonZeroU8,
 }
 
 impl<T> NanoBV<T> {
     /// Retrieve length of the current NanoBV.
     pub const fn len(&self) -> usize {
-        self.length.get()
+        self.length.get() as usize
     }
 
-    #[doc(hidden)]
+    /// Always `false`, as the length of a NanoBV is never zero.
+    #[deprecated(
+        since = "0.1.2",
+        note = "the length of a NanoBV is never zero"
+    )]
     pub const fn is_empty(&self) -> bool {
         self.length.get() == 0
     }
 }
 
+/// Errors reported by the fallible operations of this crate.
+///
+/// [`Error`] implements [`core::error::Error`], so it converts into boxed
+/// errors with `?`:
+///
+/// ```
+/// use nanobv::NanoBV;
+///
+/// fn byte(length: usize) -> Result<NanoBV<u8>, Box<dyn std::error::Error>> {
+///     Ok(NanoBV::<u8>::try_new(0xA5, length)?)
+/// }
+///
+/// assert_eq!(byte(4).unwrap(), NanoBV::<u8>::new(0x5, 4));
+/// assert_eq!(byte(9).unwrap_err().to_string(), "length zero or exceeding the size of the backing type");
+/// ```
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum Error {
+    /// The output buffer is too small.
+    BufferTooSmall,
+    /// The input is not in the expected format.
+    InvalidFormat,
+    /// The input contains a digit outside of the expected radix.
+    InvalidDigit,
+    /// The length is zero or exceeds the size of the backing type.
+    InvalidLength,
+    /// The value does not fit in the declared length.
+    Overflow,
+    /// A start or stop bit has the wrong level.
+    Framing,
+    /// A parity bit does not match the data.
+    Parity,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::BufferTooSmall => "output buffer too small",
+            Error::InvalidFormat => "input not in the expected format",
+            Error::InvalidDigit => "digit outside of the expected radix",
+            Error::InvalidLength => {
+                "length zero or exceeding the size of the backing type"
+            }
+            Error::Overflow => "value does not fit in the declared length",
+            Error::Framing => "start or stop bit at the wrong level",
+            Error::Parity => "parity bit not matching the data",
+        })
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// Rounding mode of the operations discarding low bits.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum Rounding {
+    /// Discard the low bits, rounding towards negative infinity.
+    Truncate,
+    /// Round to the nearest value, with ties rounded up.
+    Nearest,
+    /// Round to the nearest value, with ties rounded to even.
+    NearestEven,
+}
+
+/// Iterator over the offsets of the set bits of a [`NanoBV`], starting from
+/// the least significant bit.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct SetBits<T = u32> {
+    data: T,
+}
+
+/// Iterator over the [`NanoBV`]s of a given length with a given number of set
+/// bits, in increasing order.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Combinations<T = u32> {
+    next: Option<NanoBV<T>>,
+}
+
+/// Iterator over every [`NanoBV`] of a given length, in increasing order.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Values<T = u32> {
+    next: Option<NanoBV<T>>,
+}
+
+/// Iterator over the number of set bits in each window of a [`NanoBV`],
+/// starting from the window at the least significant bit.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct WindowPopcounts<T = u32> {
+    bv: NanoBV<T>,
+    window: usize,
+    offset: usize,
+    count: usize,
+}
+
+/// Iterator over the overlapping windows of a [`NanoBV`], starting from the
+/// window at the least significant bit.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Windows<T = u32> {
+    bv: NanoBV<T>,
+    window: usize,
+    offset: usize,
+}
+
 macro_rules! ImplNanoBVCommon {
     (for $($type:tt),+) => {
         $(ImplNanoBVCommon!($type);)*
     };
 
     ($type:ident) => {
+        const _: &str = ["Option<NanoBV> must be the same size as NanoBV."][(size_of::<Option<NanoBV<$type>>>() != size_of::<NanoBV<$type>>()) as usize];
+
         impl NanoBV<$type> {
             const BIT_SIZE: usize = size_of::<$type>() * 8;
 
-            const fn upper_bound(length: NonZeroUsize) -> $type {
-                match length.get() {
+            const fn upper_bound(length: NonZeroU8) -> $type {
+                match length.get() as usize {
                 n if n < Self::BIT_SIZE => (1 << n) - 1,
                 _ => $type::MAX,
                 }
@@ -44,10 +231,28 @@ macro_rules! ImplNanoBVCommon {
             /// Create a new [`NanoBV`].
             pub const fn new(data: $type, length: usize) -> Self {
                 ["Invalid length provided."][((length < 1) || (length > Self::BIT_SIZE)) as usize];
-                let length = unsafe { NonZeroUsize::new_unchecked(length) };
+                let length = unsafe { NonZeroU8::new_unchecked(length as u8) };
                 NanoBV { data: data & Self::upper_bound(length), length }
             }
 
+            /// Create a new [`NanoBV`], returning [`Error::InvalidLength`] instead of
+            /// panicking when `length` is zero or exceeds the size of the stored type.
+            pub const fn try_new(data: $type, length: usize) -> Result<Self, Error> {
+                match length {
+                1..=Self::BIT_SIZE => Ok(NanoBV::<$type>::new(data, length)),
+                _ => Err(Error::InvalidLength),
+                }
+            }
+
+            /// Create a new [`NanoBV`] of the minimal length holding `value`, or of
+            /// length 1 if `value` is zero.
+            pub const fn from_value_min_len(value: $type) -> Self {
+                match Self::BIT_SIZE - value.leading_zeros() as usize {
+                0 => NanoBV::<$type>::new(value, 1),
+                length => NanoBV::<$type>::new(value, length),
+                }
+            }
+
             /// Create a [`NanoBV`] initialized to 0 with length equivalent to the size of the
             /// stored type.
             pub const fn default() -> Self {
@@ -59,6 +264,12 @@ macro_rules! ImplNanoBVCommon {
                 self.data
             }
 
+            /// Retrieve value of the current NanoBV interpreted as two's complement.
+            pub const fn signed_value(&self) -> i64 {
+                let shift = 64 - self.len();
+                ((self.data as u64) << shift) as i64 >> shift
+            }
+
             /// Set value of the current NanoBV while retaining length.
             pub const fn set_value(&self, value: $type) -> Self {
                 let new_value = value & Self::upper_bound(self.length);
@@ -74,7 +285,7 @@ macro_rules! ImplNanoBVCommon {
             /// Create [`NanoBV`] with all bits set.
             pub const fn ones(length: usize) -> Self {
                 ["Invalid length provided."][((length < 1) || (length > Self::BIT_SIZE)) as usize];
-                NanoBV::<$type>::new(Self::upper_bound(unsafe { NonZeroUsize::new_unchecked(length) }), length)
+                NanoBV::<$type>::new(Self::upper_bound(unsafe { NonZeroU8::new_unchecked(length as u8) }), length)
             }
 
             /// Clear all bits.
@@ -87,12 +298,97 @@ macro_rules! ImplNanoBVCommon {
                 Self::ones(self.len())
             }
 
+            /// Count set bits.
+            pub const fn count_ones(&self) -> usize {
+                self.data.count_ones() as usize
+            }
+
+            /// Count unset bits within the declared length.
+            pub const fn count_zeros(&self) -> usize {
+                self.len() - self.count_ones()
+            }
+
+            /// Check whether all bits within the declared length are set.
+            pub const fn all(&self) -> bool {
+                self.data == Self::upper_bound(self.length)
+            }
+
+            /// Check whether any bit is set.
+            pub const fn any(&self) -> bool {
+                self.data != 0
+            }
+
+            /// Check whether no bit is set.
+            pub const fn none(&self) -> bool {
+                self.data == 0
+            }
+
+            /// Retrieve the offset of the highest set bit plus one, or 0 if no bit is
+            /// set. This is the minimal length able to hold the value.
+            pub const fn significant_bits(&self) -> usize {
+                Self::BIT_SIZE - self.data.leading_zeros() as usize
+            }
+
+            /// Isolate the lowest set bit, as BMI1 `blsi`. Zero is returned
+            /// unchanged.
+            pub const fn blsi(&self) -> Self {
+                NanoBV::<$type>::new(self.data & self.data.wrapping_neg(), self.len())
+            }
+
+            /// Reset the lowest set bit, as BMI1 `blsr`. Zero is returned unchanged.
+            pub const fn blsr(&self) -> Self {
+                NanoBV::<$type>::new(self.data & self.data.wrapping_sub(1), self.len())
+            }
+
+            /// Set all bits up to and including the lowest set bit, clearing the
+            /// others, as BMI1 `blsmsk`. Zero yields all bits set.
+            pub const fn blsmsk(&self) -> Self {
+                NanoBV::<$type>::new(self.data ^ self.data.wrapping_sub(1), self.len())
+            }
+
+            /// Clear the bits from offset `index` upwards, as BMI2 `bzhi`. An `index`
+            /// at or beyond the length leaves the value unchanged.
+            pub const fn bzhi(&self, index: usize) -> Self {
+                match index {
+                0 => NanoBV::<$type>::zeros(self.len()),
+                _ if index >= self.len() => *self,
+                _ => NanoBV::<$type>::new(self.data & NanoBV::<$type>::ones(index).data, self.len()),
+                }
+            }
+
+            /// const_fn alternative to [`PartialEq::eq`], comparing both value and
+            /// length.
+            pub const fn const_eq(&self, rhs: Self) -> bool {
+                self.data == rhs.data && self.len() == rhs.len()
+            }
+
+            /// Compare by value, then by length, in a const context.
+            pub const fn const_cmp(&self, rhs: Self) -> Ordering {
+                match (self.data, self.len()) {
+                (data, _) if data < rhs.data => Ordering::Less,
+                (data, _) if data > rhs.data => Ordering::Greater,
+                (_, len) if len < rhs.len() => Ordering::Less,
+                (_, len) if len > rhs.len() => Ordering::Greater,
+                _ => Ordering::Equal,
+                }
+            }
+
+            /// Compare values, ignoring lengths.
+            pub const fn eq_value(&self, rhs: Self) -> bool {
+                self.data == rhs.data
+            }
+
             /// Get bit at offset.
             pub const fn get_bit(&self, offset: $type) -> $type {
                 ["Invalid offset provided."][(offset as usize >= self.len()) as usize];
                 (self.data >> offset) & 1
             }
 
+            /// Check whether the bit at offset is set.
+            pub const fn test_bit(&self, offset: $type) -> bool {
+                self.get_bit(offset) == 1
+            }
+
             /// Set bit at offset.
             pub const fn set_bit(&self, offset: $type) -> Self {
                 ["Invalid offset provided."][(offset as usize >= self.len()) as usize];
@@ -116,6 +412,65 @@ macro_rules! ImplNanoBVCommon {
                 }
             }
 
+            /// Get bit at offset, numbering bits from the most significant bit.
+            pub const fn get_bit_msb0(&self, offset: $type) -> $type {
+                ["Invalid offset provided."][(offset as usize >= self.len()) as usize];
+                self.get_bit((self.len() - 1) as $type - offset)
+            }
+
+            /// Set bit at offset, numbering bits from the most significant bit.
+            pub const fn set_bit_msb0(&self, offset: $type) -> Self {
+                ["Invalid offset provided."][(offset as usize >= self.len()) as usize];
+                self.set_bit((self.len() - 1) as $type - offset)
+            }
+
+            /// Clear bit at offset, numbering bits from the most significant bit.
+            pub const fn clear_bit_msb0(&self, offset: $type) -> Self {
+                ["Invalid offset provided."][(offset as usize >= self.len()) as usize];
+                self.clear_bit((self.len() - 1) as $type - offset)
+            }
+
+            /// Assign bit at offset, numbering bits from the most significant bit.
+            pub const fn assign_bit_msb0(&self, value: $type, offset: $type) -> Self {
+                ["Invalid offset provided."][(offset as usize >= self.len()) as usize];
+                self.assign_bit(value, (self.len() - 1) as $type - offset)
+            }
+
+            /// Set value of the current NanoBV in place while retaining length.
+            pub fn set_value_mut(&mut self, value: $type) {
+                *self = self.set_value(value);
+            }
+
+            /// Clear all bits in place.
+            pub fn clear_mut(&mut self) {
+                *self = self.clear();
+            }
+
+            /// Set all bits in place.
+            pub fn set_mut(&mut self) {
+                *self = self.set();
+            }
+
+            /// Set bit at offset in place.
+            pub fn set_bit_mut(&mut self, offset: $type) {
+                *self = self.set_bit(offset);
+            }
+
+            /// Clear bit at offset in place.
+            pub fn clear_bit_mut(&mut self, offset: $type) {
+                *self = self.clear_bit(offset);
+            }
+
+            /// Assign bit at offset in place.
+            pub fn assign_bit_mut(&mut self, value: $type, offset: $type) {
+                *self = self.assign_bit(value, offset);
+            }
+
+            /// Reverse bits in place.
+            pub fn reverse_mut(&mut self) {
+                *self = self.reverse();
+            }
+
             /// Reverse bits.
             pub const fn reverse(&self) -> Self {
                 let mut reversed = self.data.reverse_bits();
@@ -123,9 +478,499 @@ macro_rules! ImplNanoBVCommon {
                 NanoBV::<$type>::new(reversed, self.len())
             }
 
+            /// Reverse the lowest `count` bits, retaining the bits above them, as in
+            /// the bit-reversed addressing of a 2^`count`-point FFT.
+            pub const fn reverse_low(&self, count: usize) -> Self {
+                ["Invalid length provided."][((count < 1) || (count > self.len())) as usize];
+                let low = NanoBV::<$type>::ones(count).data;
+                let reversed = (self.data & low).reverse_bits() >> (Self::BIT_SIZE - count);
+                NanoBV::<$type>::new((self.data & !low) | reversed, self.len())
+            }
+
+            /// Reverse the bit order within each byte, retaining the byte order, as
+            /// for reflected CRCs and octets transmitted least significant bit first.
+            ///
+            /// The length must be a multiple of 8.
+            pub const fn reflect_bytes(&self) -> Self {
+                ["Invalid length provided."][(self.len() % 8 != 0) as usize];
+                NanoBV::<$type>::new(self.data.reverse_bits().swap_bytes(), self.len())
+            }
+
+            /// Permute bits, taking output bit `i` from source bit `table[i]`.
+            ///
+            /// Returns `None` unless `table` is a permutation of `0..self.len()`.
+            pub const fn permute(&self, table: &[u8]) -> Option<Self> {
+                if table.len() != self.len() {
+                    return None;
+                }
+                let (mut seen, mut permuted, mut i) = (0u64, 0, 0);
+                while i < table.len() {
+                    let source = table[i] as usize;
+                    if source >= self.len() || (seen >> source) & 1 == 1 {
+                        return None;
+                    }
+                    seen |= 1 << source;
+                    permuted |= ((self.data >> source) & 1) << i;
+                    i += 1;
+                }
+                Some(NanoBV::<$type>::new(permuted, self.len()))
+            }
+
+            /// Gather bits, taking output bit `i` from source bit `indices[i]`. The
+            /// length of the result is the number of indices.
+            pub const fn gather(&self, indices: &[u8]) -> Self {
+                let (mut gathered, mut i) = (0, 0);
+                while i < indices.len() {
+                    ["Invalid offset provided."][(indices[i] as usize >= self.len()) as usize];
+                    gathered |= ((self.data >> indices[i]) & 1) << i;
+                    i += 1;
+                }
+                NanoBV::<$type>::new(gathered, indices.len())
+            }
+
+            /// Scatter bits, writing bit `i` of `bits` to offset `indices[i]`. Bits
+            /// at offsets not listed are retained.
+            pub const fn scatter(&self, indices: &[u8], bits: Self) -> Self {
+                ["Invalid length provided."][(indices.len() > bits.len()) as usize];
+                let (mut scattered, mut i) = (*self, 0);
+                while i < indices.len() {
+                    ["Invalid offset provided."][(indices[i] as usize >= self.len()) as usize];
+                    scattered = scattered.assign_bit(bits.get_bit(i as $type), indices[i] as $type);
+                    i += 1;
+                }
+                scattered
+            }
+
+            /// Extract every `n`-th bit starting from offset `phase`, as when one of `n`
+            /// interleaved channels is sampled into the current NanoBV. The length of
+            /// the result is the number of bits extracted.
+            pub const fn every_nth(&self, n: usize, phase: usize) -> Self {
+                ["Invalid phase provided."][((n < 1) || (phase >= n) || (phase >= self.len())) as usize];
+                let (mut extracted, mut i) = (0, 0);
+                while phase + i * n < self.len() {
+                    extracted |= ((self.data >> (phase + i * n)) & 1) << i;
+                    i += 1;
+                }
+                NanoBV::<$type>::new(extracted, i)
+            }
+
+            /// Expand into a NanoBV of `length` bits, writing bit `i` to offset
+            /// `phase + i * n` and clearing the others. Inverse of
+            /// [`every_nth`](Self::every_nth) for a single channel.
+            pub const fn expand_every_nth(&self, n: usize, phase: usize, length: usize) -> Self {
+                ["Invalid phase provided."][((n < 1) || (phase >= n)) as usize];
+                ["Invalid length provided."][(phase + (self.len() - 1) * n >= length) as usize];
+                let (mut expanded, mut i) = (0, 0);
+                while i < self.len() {
+                    expanded |= ((self.data >> i) & 1) << (phase + i * n);
+                    i += 1;
+                }
+                NanoBV::<$type>::new(expanded, length)
+            }
+
+            /// Insert a zero bit above each bit, doubling the length, so that two
+            /// spread coordinates interleave into a 2D Morton code.
+            pub const fn spread2(&self) -> Self {
+                self.expand_every_nth(2, 0, 2 * self.len())
+            }
+
+            /// Insert two zero bits above each bit, tripling the length, so that
+            /// three spread coordinates interleave into a 3D Morton code.
+            pub const fn spread3(&self) -> Self {
+                self.expand_every_nth(3, 0, 3 * self.len())
+            }
+
+            /// Set each bit to the XOR of itself and all less significant bits, using
+            /// O(log n) shifts as in a Kogge-Stone adder.
+            pub const fn prefix_xor(&self) -> Self {
+                let (mut scan, mut shift) = (self.data, 1);
+                while shift < self.len() {
+                    scan ^= scan << shift;
+                    shift *= 2;
+                }
+                NanoBV::<$type>::new(scan, self.len())
+            }
+
+            /// Set each bit to the OR of itself and all less significant bits, using
+            /// O(log n) shifts as in a Kogge-Stone adder.
+            pub const fn prefix_or(&self) -> Self {
+                let (mut scan, mut shift) = (self.data, 1);
+                while shift < self.len() {
+                    scan |= scan << shift;
+                    shift *= 2;
+                }
+                NanoBV::<$type>::new(scan, self.len())
+            }
+
+            /// Set each bit to the AND of itself and all less significant bits, using
+            /// O(log n) shifts as in a Kogge-Stone adder.
+            pub const fn prefix_and(&self) -> Self {
+                let (mut scan, mut shift) = (self.data, 1);
+                while shift < self.len() {
+                    scan &= (scan << shift) | NanoBV::<$type>::ones(shift).data;
+                    shift *= 2;
+                }
+                NanoBV::<$type>::new(scan, self.len())
+            }
+
+            /// Keep every other bit starting from the least significant one. Inverse
+            /// of [`spread2`](Self::spread2).
+            pub const fn compact2(&self) -> Self {
+                self.every_nth(2, 0)
+            }
+
+            /// Keep every third bit starting from the least significant one. Inverse
+            /// of [`spread3`](Self::spread3).
+            pub const fn compact3(&self) -> Self {
+                self.every_nth(3, 0)
+            }
+
+            /// Apply `f` to each byte lane covered by the length, starting from the
+            /// least significant byte. A partial top lane is passed zero extended,
+            /// and the result is truncated back to the length.
+            pub fn map_bytes(&self, f: fn(u8) -> u8) -> Self {
+                self.map_lanes(8, f)
+            }
+
+            /// Apply `f` to each nibble lane covered by the length, as in
+            /// [`map_bytes`](Self::map_bytes). Only the low nibble of the result of
+            /// `f` is kept.
+            pub fn map_nibbles(&self, f: fn(u8) -> u8) -> Self {
+                self.map_lanes(4, f)
+            }
+
+            /// Apply `f` to each lane of `width` bits covered by the length.
+            fn map_lanes(&self, width: usize, f: fn(u8) -> u8) -> Self {
+                let mask = u8::MAX >> (8 - width);
+                let mapped = (0..self.len()).step_by(width).fold(0, |mapped, offset| {
+                    let lane = f((self.data >> offset) as u8 & mask) & mask;
+                    mapped | (lane as $type) << offset
+                });
+                self.set_value(mapped)
+            }
+
+            /// Shift left by `amount`, shifting in `fill` bits.
+            pub const fn shl_fill(&self, amount: usize, fill: bool) -> Self {
+                match (amount, fill) {
+                (0, _) => *self,
+                (n, false) if n < self.len() => self.set_value(self.data << n),
+                (n, true) if n < self.len() => self.set_value((self.data << n) | NanoBV::<$type>::ones(n).data),
+                (_, false) => self.clear(),
+                (_, true) => self.set(),
+                }
+            }
+
+            /// Shift right by `amount`, shifting in `fill` bits.
+            pub const fn shr_fill(&self, amount: usize, fill: bool) -> Self {
+                match (amount, fill) {
+                (0, _) => *self,
+                (n, false) if n < self.len() => self.set_value(self.data >> n),
+                (n, true) if n < self.len() => self.set_value((self.data >> n) | !(Self::upper_bound(self.length) >> n)),
+                (_, false) => self.clear(),
+                (_, true) => self.set(),
+                }
+            }
+
+            /// Insert bit at offset, shifting the higher bits up and growing the
+            /// length by one.
+            pub const fn insert_bit(&self, offset: $type, value: $type) -> Self {
+                ["Invalid length provided."][(self.len() >= Self::BIT_SIZE) as usize];
+                ["Invalid offset provided."][(offset as usize > self.len()) as usize];
+                let low = ((1 as $type) << offset) - 1;
+                let high = (self.data & !low) << 1;
+                NanoBV::<$type>::new(high | ((value & 1) << offset) | (self.data & low), self.len() + 1)
+            }
+
+            /// Remove bit at offset, shifting the higher bits down and shrinking the
+            /// length by one. Returns the removed bit and the shortened NanoBV.
+            pub const fn remove_bit(&self, offset: $type) -> ($type, Self) {
+                ["Invalid length provided."][(self.len() <= 1) as usize];
+                ["Invalid offset provided."][(offset as usize >= self.len()) as usize];
+                let low = ((1 as $type) << offset) - 1;
+                let high = (self.data >> 1) & !low;
+                (self.get_bit(offset), NanoBV::<$type>::new(high | (self.data & low), self.len() - 1))
+            }
+
+            /// Gather all set bits towards the least significant bit. The number of
+            /// set bits is retained, see [`count_ones`](Self::count_ones).
+            pub const fn compress_low(&self) -> Self {
+                match self.count_ones() {
+                0 => *self,
+                count => NanoBV::<$type>::new(NanoBV::<$type>::ones(count).data, self.len()),
+                }
+            }
+
+            /// Gather all set bits towards the most significant bit. The number of
+            /// set bits is retained, see [`count_ones`](Self::count_ones).
+            pub const fn compress_high(&self) -> Self {
+                match self.count_ones() {
+                0 => *self,
+                count => NanoBV::<$type>::new(NanoBV::<$type>::ones(count).data << (self.len() - count), self.len()),
+                }
+            }
+
+            /// Absolute difference of the unsigned values, truncated to the shorter
+            /// length first.
+            pub const fn abs_diff(&self, rhs: Self) -> Self {
+                let length = $crate::internals::min(self.len(), rhs.len());
+                let mask = NanoBV::<$type>::ones(length).data;
+                NanoBV::<$type>::new((self.data & mask).abs_diff(rhs.data & mask), length)
+            }
+
+            /// Average of the unsigned values rounded down, truncated to the shorter
+            /// length first. The sum is never formed, so this does not overflow.
+            pub const fn midpoint(&self, rhs: Self) -> Self {
+                let length = $crate::internals::min(self.len(), rhs.len());
+                let mask = NanoBV::<$type>::ones(length).data;
+                let (a, b) = (self.data & mask, rhs.data & mask);
+                NanoBV::<$type>::new((a & b) + ((a ^ b) >> 1), length)
+            }
+
+            /// Multiplicative inverse modulo `2^len`, or `None` if the value is even.
+            pub const fn mod_inverse(&self) -> Option<Self> {
+                if self.data & 1 == 0 {
+                    return None;
+                }
+                // Each Newton step doubles the number of correct low bits, starting
+                // from the 3 bits given by `a * a == 1 (mod 8)`.
+                let (mut inverse, mut correct) = (self.data, 3);
+                while correct < self.len() {
+                    inverse = inverse.wrapping_mul((2 as $type).wrapping_sub(self.data.wrapping_mul(inverse)));
+                    correct *= 2;
+                }
+                Some(NanoBV::<$type>::new(inverse, self.len()))
+            }
+
+            /// Multiply-accumulate, computing `self + a * b`.
+            ///
+            /// The result keeps the length of `self` and wraps modulo `2^len`
+            /// instead of panicking on overflow.
+            pub const fn mac(&self, a: Self, b: Self) -> Self {
+                self.set_value(self.data.wrapping_add(a.data.wrapping_mul(b.data)))
+            }
+
+            /// Count the bits set in both NanoBVs, within the shorter length.
+            pub const fn dot(&self, rhs: Self) -> usize {
+                self.bvand(rhs).count_ones()
+            }
+
+            /// Jaccard similarity of the set bits, within the shorter length.
+            ///
+            /// Two NanoBVs without any set bits are identical, with a similarity of 1.
+            pub fn jaccard(&self, rhs: Self) -> f32 {
+                match self.bvor(rhs).count_ones() {
+                0 => 1.0,
+                union => self.dot(rhs) as f32 / union as f32,
+                }
+            }
+
+            /// Select bits from `a` where `mask` is set and from `b` elsewhere.
+            ///
+            /// All three NanoBVs must have the same length.
+            pub const fn blend(mask: Self, a: Self, b: Self) -> Self {
+                ["Invalid length provided."][((mask.len() != a.len()) || (mask.len() != b.len())) as usize];
+                NanoBV::<$type>::new((a.data & mask.data) | (b.data & !mask.data), mask.len())
+            }
+
+            /// Check whether the current NanoBV matches `value` on every bit set in
+            /// `care`, as a TCAM entry would.
+            ///
+            /// All three NanoBVs must have the same length.
+            pub const fn matches_masked(&self, value: Self, care: Self) -> bool {
+                ["Invalid length provided."][((self.len() != value.len()) || (self.len() != care.len())) as usize];
+                (self.data ^ value.data) & care.data == 0
+            }
+
+            /// Create a one-hot [`NanoBV`] with only the bit at `index` set.
+            pub const fn one_hot(index: $type, length: usize) -> Self {
+                NanoBV::<$type>::zeros(length).set_bit(index)
+            }
+
+            /// Retrieve the index of the set bit, or `None` unless exactly one bit
+            /// is set.
+            pub const fn decode_one_hot(&self) -> Option<$type> {
+                match self.data.is_power_of_two() {
+                true => Some(self.data.trailing_zeros() as $type),
+                false => None,
+                }
+            }
+
+            /// Create a thermometer-coded [`NanoBV`] with the lowest `count` bits set.
+            pub const fn thermometer(count: usize, length: usize) -> Self {
+                ["Invalid length provided."][(count > length) as usize];
+                match count {
+                0 => NanoBV::<$type>::zeros(length),
+                _ => NanoBV::<$type>::new(NanoBV::<$type>::ones(count).data, length),
+                }
+            }
+
+            /// Retrieve the number of set bits of a thermometer code, or `None` if
+            /// the set bits are not contiguous from the least significant bit.
+            pub const fn decode_thermometer(&self) -> Option<usize> {
+                match self.data & self.data.wrapping_add(1) {
+                0 => Some(self.count_ones()),
+                _ => None,
+                }
+            }
+
+            /// Count the redundant sign bits, i.e. the copies of the most significant
+            /// bit following it, within the declared length.
+            pub const fn leading_sign_bits(&self) -> usize {
+                let value = self.signed_value();
+                ((value ^ (value >> 63)).leading_zeros() as usize - 1) - (64 - self.len())
+            }
+
+            /// Shift left until the most significant bit of the declared length is
+            /// set, returning the shifted NanoBV and the shift count, or `None` if
+            /// no bit is set.
+            pub const fn normalize(&self) -> Option<(Self, usize)> {
+                match self.data {
+                0 => None,
+                data => {
+                    let shift = self.len() - (Self::BIT_SIZE - data.leading_zeros() as usize);
+                    Some((self.set_value(data << shift), shift))
+                }
+                }
+            }
+
+            /// Count the transitions between adjacent bits within the declared
+            /// length.
+            pub const fn transitions(&self) -> usize {
+                ((self.data ^ (self.data >> 1)) & (Self::upper_bound(self.length) >> 1)).count_ones() as usize
+            }
+
+            /// Check whether the bits read the same reversed within the declared
+            /// length.
+            pub const fn is_palindrome(&self) -> bool {
+                self.reverse().data == self.data
+            }
+
+            /// Compute the bitwise majority of three NanoBVs, as used for triple
+            /// modular redundancy voting.
+            ///
+            /// All three NanoBVs must have the same length.
+            pub const fn majority(a: Self, b: Self, c: Self) -> Self {
+                ["Invalid length provided."][((a.len() != b.len()) || (a.len() != c.len())) as usize];
+                NanoBV::<$type>::new((a.data & b.data) | (a.data & c.data) | (b.data & c.data), a.len())
+            }
+
+            /// Retrieve the next larger NanoBV with the same number of set bits, or
+            /// `None` if there is none within the declared length.
+            pub const fn next_with_same_popcount(&self) -> Option<Self> {
+                if self.data == 0 {
+                    return None;
+                }
+                let lowest = self.data & self.data.wrapping_neg();
+                let ripple = match self.data.checked_add(lowest) {
+                Some(ripple) => ripple,
+                None => return None,
+                };
+                let next = (((ripple ^ self.data) >> 2) >> lowest.trailing_zeros()) | ripple;
+                match next > Self::upper_bound(self.length) {
+                true => None,
+                false => Some(NanoBV::<$type>::new(next, self.len())),
+                }
+            }
+
+            /// Iterate over the NanoBVs of `length` bits with `count` set bits, in
+            /// increasing order.
+            pub const fn combinations(count: usize, length: usize) -> Combinations<$type> {
+                Combinations { next: Some(NanoBV::<$type>::thermometer(count, length)) }
+            }
+
+            /// Iterate over every NanoBV of `length` bits, in increasing order.
+            pub const fn all_values(length: usize) -> Values<$type> {
+                Values { next: Some(NanoBV::<$type>::zeros(length)) }
+            }
+
+            /// Iterate over the number of set bits in each `window`-bit window within
+            /// the declared length, moving one bit at a time from the least
+            /// significant bit. Each count is derived from the previous one.
+            pub const fn window_popcounts(&self, window: usize) -> WindowPopcounts<$type> {
+                ["Invalid length provided."][((window < 1) || (window > self.len())) as usize];
+                let count = (self.data & NanoBV::<$type>::ones(window).data).count_ones() as usize;
+                WindowPopcounts { bv: *self, window, offset: 0, count }
+            }
+
+            /// Iterate over each `window`-bit sub-vector within the declared length,
+            /// moving one bit at a time from the least significant bit.
+            pub const fn windows(&self, window: usize) -> Windows<$type> {
+                ["Invalid length provided."][((window < 1) || (window > self.len())) as usize];
+                Windows { bv: *self, window, offset: 0 }
+            }
+
+            /// Resize to `length`, clamping the value to the largest value of the
+            /// new length instead of truncating it when shrinking.
+            pub const fn resize_saturating(&self, length: usize) -> Self {
+                let saturated = NanoBV::<$type>::ones(length);
+                match self.data > saturated.data {
+                true => saturated,
+                false => NanoBV::<$type>::new(self.data, length),
+                }
+            }
+
+            /// Shift right by `amount`, rounding the discarded bits with `rounding`.
+            pub const fn shr_round(&self, amount: usize, rounding: Rounding) -> Self {
+                if amount > 64 {
+                    return self.clear();
+                }
+                self.set_value($crate::internals::round_shr(self.data as i128, amount, rounding) as $type)
+            }
+
+            /// Round the value up to a multiple of `align`, which must be a power of
+            /// two. Returns `None` if the result does not fit in the declared length.
+            pub const fn align_up(&self, align: $type) -> Option<Self> {
+                ["Invalid alignment provided."][!align.is_power_of_two() as usize];
+                let aligned = (self.data as u128 + (align - 1) as u128) & !((align - 1) as u128);
+                match aligned > Self::upper_bound(self.length) as u128 {
+                true => None,
+                false => Some(NanoBV::<$type>::new(aligned as $type, self.len())),
+                }
+            }
+
+            /// Round the value down to a multiple of `align`, which must be a power
+            /// of two.
+            pub const fn align_down(&self, align: $type) -> Self {
+                ["Invalid alignment provided."][!align.is_power_of_two() as usize];
+                NanoBV::<$type>::new(self.data & !(align - 1), self.len())
+            }
+
+            /// Create a prefix mask with the top `count` bits of `length` set, as in
+            /// a CIDR netmask.
+            pub const fn prefix_mask(count: usize, length: usize) -> Self {
+                ["Invalid length provided."][(count > length) as usize];
+                match count {
+                0 => NanoBV::<$type>::zeros(length),
+                _ => NanoBV::<$type>::new(NanoBV::<$type>::ones(count).data << (length - count), length),
+                }
+            }
+
+            /// Count the leading bits shared with `rhs`, starting from the most
+            /// significant bit.
+            ///
+            /// Both NanoBVs must have the same length.
+            pub const fn common_prefix_len(&self, rhs: Self) -> usize {
+                ["Invalid length provided."][(self.len() != rhs.len()) as usize];
+                self.len() - (Self::BIT_SIZE - (self.data ^ rhs.data).leading_zeros() as usize)
+            }
+
+            /// Write the current NanoBV as binary digits, most significant bit
+            /// first, returning the number of bytes written.
+            #[cfg(feature = "fmt")]
+            pub fn write_binary(&self, buf: &mut [u8]) -> Result<usize, Error> {
+                $crate::internals::write_digits(self.data as u64, self.len(), 1, buf)
+            }
+
+            /// Write the current NanoBV as lowercase hexadecimal digits, with
+            /// `ceil(len / 4)` digits, returning the number of bytes written.
+            #[cfg(feature = "fmt")]
+            pub fn write_hex(&self, buf: &mut [u8]) -> Result<usize, Error> {
+                $crate::internals::write_digits(self.data as u64, self.len().div_ceil(4), 4, buf)
+            }
+
             /// const_fn alternative to [`core::ops::Add`].
             pub const fn bvadd(&self, rhs: Self) -> Self {
-                NanoBV::<$type>::new(self.data + rhs.data, $crate::internals::min(self.len(), rhs.len()))
+                NanoBV::<$type>::new(self.data.wrapping_add(rhs.data), $crate::internals::min(self.len(), rhs.len()))
             }
 
             /// const_fn alternative to [`core::ops::BitAnd`].
@@ -144,23 +989,30 @@ macro_rules! ImplNanoBVCommon {
             }
 
             /// const_fn alternative to [`core::ops::Div`].
+            #[cfg(feature = "div")]
             pub const fn bvdiv(&self, rhs: Self) -> Self {
                 NanoBV::<$type>::new(self.data / rhs.data, $crate::internals::min(self.len(), rhs.len()))
             }
 
             /// const_fn alternative to [`core::ops::Mul`].
             pub const fn bvmul(&self, rhs: Self) -> Self {
-                NanoBV::<$type>::new(self.data * rhs.data, $crate::internals::min(self.len(), rhs.len()))
+                NanoBV::<$type>::new(self.data.wrapping_mul(rhs.data), $crate::internals::min(self.len(), rhs.len()))
             }
 
             /// const_fn alternative to [`core::ops::Rem`].
+            #[cfg(feature = "div")]
             pub const fn bvrem(&self, rhs: Self) -> Self {
                 NanoBV::<$type>::new(self.data % rhs.data, $crate::internals::min(self.len(), rhs.len()))
             }
 
-            /// const_fn alternative to [`core::ops::Shl`].
+            /// const_fn alternative to [`core::ops::Shl`], yielding zero for shifts of
+            /// at least the length as SMT-LIB `bvshl` does.
             pub const fn bvshl(&self, rhs: Self) -> Self {
-                NanoBV::<$type>::new(self.data << rhs.data, $crate::internals::min(self.len(), rhs.len()))
+                let length = $crate::internals::min(self.len(), rhs.len());
+                match rhs.data as u64 >= length as u64 {
+                true => NanoBV::<$type>::zeros(length),
+                false => NanoBV::<$type>::new(self.data << rhs.data, length),
+                }
             }
 
             /// const_fn alternative to [`core::ops::Shr`].
@@ -170,42 +1022,329 @@ macro_rules! ImplNanoBVCommon {
 
             /// const_fn alternative to [`core::ops::Sub`].
             pub const fn bvsub(&self, rhs: Self) -> Self {
-                NanoBV::<$type>::new(self.data - rhs.data, $crate::internals::min(self.len(), rhs.len()))
+                NanoBV::<$type>::new(self.data.wrapping_sub(rhs.data), $crate::internals::min(self.len(), rhs.len()))
+            }
+
+            /// Iterate over the offsets of the set bits.
+            pub const fn iter_set_bits(&self) -> SetBits<$type> {
+                SetBits { data: self.data }
             }
         }
-    };
-}
 
-ImplNanoBVCommon!(for u8, u16, u32, u64);
+        impl Iterator for SetBits<$type> {
+            type Item = $type;
 
-macro_rules! ImplNanoBVOps {
-    (for $(($trait:tt, $function:tt)),+) => {
-        $(ImplNanoBVOps!($trait, $function);)*
-    };
+            fn next(&mut self) -> Option<$type> {
+                match self.data {
+                0 => None,
+                data => {
+                    self.data &= data - 1;
+                    Some(data.trailing_zeros() as $type)
+                }
+                }
+            }
 
-    ($trait:ident, $function:ident) => {
-        impl<T: $trait + $trait<Output = T> + TryFrom<u128> + TryInto<u128> + Default> $trait for NanoBV<T> {
-            type Output = Self;
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let count = self.data.count_ones() as usize;
+                (count, Some(count))
+            }
+        }
 
-            fn $function(self, other: Self) -> Self {
-                let length = $crate::internals::min(self.len(), other.len());
-                let data = T::try_from(self.data.$function(other.data).try_into().unwrap_or_default() & ((1u128 << length) - 1)).unwrap_or_default();
-                Self { data, length: NonZeroUsize::new(length).unwrap() }
+        impl ExactSizeIterator for SetBits<$type> {}
+
+        impl Iterator for Combinations<$type> {
+            type Item = NanoBV<$type>;
+
+            fn next(&mut self) -> Option<NanoBV<$type>> {
+                let current = self.next?;
+                self.next = current.next_with_same_popcount();
+                Some(current)
             }
         }
-    };
-}
 
-ImplNanoBVOps!(for (Add, add), (BitAnd, bitand), (BitOr, bitor), (BitXor, bitxor), (Div, div), (Mul, mul), (Rem, rem), (Shl, shl), (Shr, shr), (Sub, sub));
+        impl Iterator for Values<$type> {
+            type Item = NanoBV<$type>;
 
-#[doc(hidden)]
-pub mod internals {
-    pub const fn min(a: usize, b: usize) -> usize {
-        [a, b][(a >= b) as usize]
-    }
-}
+            fn next(&mut self) -> Option<NanoBV<$type>> {
+                let current = self.next?;
+                self.next = match current.data == NanoBV::<$type>::upper_bound(current.length) {
+                true => None,
+                false => Some(current.set_value(current.data + 1)),
+                };
+                Some(current)
+            }
+        }
 
-#[cfg(test)]
+        impl Iterator for WindowPopcounts<$type> {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                let end = self.offset + self.window;
+                if end > self.bv.len() {
+                    return None;
+                }
+                let current = self.count;
+                if end < self.bv.len() {
+                    self.count = self.count + (self.bv.data >> end) as usize % 2 - (self.bv.data >> self.offset) as usize % 2;
+                }
+                self.offset += 1;
+                Some(current)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let remaining = (self.bv.len() + 1).saturating_sub(self.offset + self.window);
+                (remaining, Some(remaining))
+            }
+        }
+
+        impl ExactSizeIterator for WindowPopcounts<$type> {}
+
+        impl Iterator for Windows<$type> {
+            type Item = NanoBV<$type>;
+
+            fn next(&mut self) -> Option<NanoBV<$type>> {
+                if self.offset + self.window > self.bv.len() {
+                    return None;
+                }
+                let current = NanoBV::<$type>::new((self.bv.data >> self.offset) & NanoBV::<$type>::ones(self.window).data, self.window);
+                self.offset += 1;
+                Some(current)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let remaining = (self.bv.len() + 1).saturating_sub(self.offset + self.window);
+                (remaining, Some(remaining))
+            }
+        }
+
+        impl ExactSizeIterator for Windows<$type> {}
+    };
+}
+
+ImplNanoBVCommon!(for u8, u16, u32, u64);
+
+macro_rules! ImplNanoBVOps {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVOps!(@list () $type);)*
+    };
+
+    (const for $($type:tt),+) => {
+        $(ImplNanoBVOps!(@list (const) $type);)*
+    };
+
+    (@list $constness:tt $type:ident) => {
+        ImplNanoBVOps!($constness $type, (Add, add, AddAssign, add_assign, bvadd), (BitAnd, bitand, BitAndAssign, bitand_assign, bvand), (BitOr, bitor, BitOrAssign, bitor_assign, bvor), (BitXor, bitxor, BitXorAssign, bitxor_assign, bvxor), (#[cfg(feature = "div")] Div, div, DivAssign, div_assign, bvdiv), (Mul, mul, MulAssign, mul_assign, bvmul), (#[cfg(feature = "div")] Rem, rem, RemAssign, rem_assign, bvrem), (Shl, shl, ShlAssign, shl_assign, bvshl), (Shr, shr, ShrAssign, shr_assign, bvshr), (Sub, sub, SubAssign, sub_assign, bvsub));
+    };
+
+    ($constness:tt $type:ident, $(($(#[$attr:meta])* $trait:ident, $function:ident, $assign_trait:ident, $assign_function:ident, $bvfunction:ident)),+) => {
+        $(ImplNanoBVOps!(@impl $constness $type, $(#[$attr])* $trait, $function, $assign_trait, $assign_function, $bvfunction);)*
+    };
+
+    (@impl ($($constness:ident)?) $type:ident, $(#[$attr:meta])* $trait:ident, $function:ident, $assign_trait:ident, $assign_function:ident, $bvfunction:ident) => {
+        $(#[$attr])*
+        impl $($constness)? $trait for NanoBV<$type> {
+            type Output = Self;
+
+            fn $function(self, other: Self) -> Self {
+                self.$bvfunction(other)
+            }
+        }
+
+        $(#[$attr])*
+        impl $($constness)? $assign_trait for NanoBV<$type> {
+            fn $assign_function(&mut self, other: Self) {
+                *self = self.$bvfunction(other);
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "const_trait_impl"))]
+ImplNanoBVOps!(for u8, u16, u32, u64);
+#[cfg(feature = "const_trait_impl")]
+ImplNanoBVOps!(const for u8, u16, u32, u64);
+
+macro_rules! ImplNanoBVMixedOps {
+    (for $(($narrow:ident, $wide:ident)),+) => {
+        $(ImplNanoBVMixedOps!($narrow, $wide, (Add, add, AddAssign, add_assign, bvadd), (BitAnd, bitand, BitAndAssign, bitand_assign, bvand), (BitOr, bitor, BitOrAssign, bitor_assign, bvor), (BitXor, bitxor, BitXorAssign, bitxor_assign, bvxor), (#[cfg(feature = "div")] Div, div, DivAssign, div_assign, bvdiv), (Mul, mul, MulAssign, mul_assign, bvmul), (#[cfg(feature = "div")] Rem, rem, RemAssign, rem_assign, bvrem), (Shl, shl, ShlAssign, shl_assign, bvshl), (Shr, shr, ShrAssign, shr_assign, bvshr), (Sub, sub, SubAssign, sub_assign, bvsub));)*
+    };
+
+    ($narrow:ident, $wide:ident, $(($(#[$attr:meta])* $trait:ident, $function:ident, $assign_trait:ident, $assign_function:ident, $bvfunction:ident)),+) => {
+        impl From<NanoBV<$narrow>> for NanoBV<$wide> {
+            fn from(bv: NanoBV<$narrow>) -> Self {
+                NanoBV { data: bv.data as $wide, length: bv.length }
+            }
+        }
+
+        $($(#[$attr])*
+        impl $trait<NanoBV<$narrow>> for NanoBV<$wide> {
+            type Output = Self;
+
+            fn $function(self, other: NanoBV<$narrow>) -> Self {
+                self.$bvfunction(other.into())
+            }
+        }
+
+        $(#[$attr])*
+        impl $trait<NanoBV<$wide>> for NanoBV<$narrow> {
+            type Output = NanoBV<$wide>;
+
+            fn $function(self, other: NanoBV<$wide>) -> NanoBV<$wide> {
+                NanoBV::<$wide>::from(self).$bvfunction(other)
+            }
+        }
+
+        $(#[$attr])*
+        impl $assign_trait<NanoBV<$narrow>> for NanoBV<$wide> {
+            fn $assign_function(&mut self, other: NanoBV<$narrow>) {
+                *self = self.$bvfunction(other.into());
+            }
+        })*
+    };
+}
+
+ImplNanoBVMixedOps!(for (u8, u16), (u8, u32), (u8, u64), (u16, u32), (u16, u64), (u32, u64));
+
+#[doc(hidden)]
+pub mod internals {
+    #[cfg(feature = "fmt")]
+    use crate::Error;
+    use crate::Rounding;
+
+    pub const fn min(a: usize, b: usize) -> usize {
+        [a, b][(a >= b) as usize]
+    }
+
+    /// Shift `value` right by at most 64 bits, rounding the discarded bits.
+    pub const fn round_shr(
+        value: i128,
+        amount: usize,
+        rounding: Rounding,
+    ) -> i128 {
+        if amount == 0 {
+            return value;
+        }
+        let (quotient, remainder, half) =
+            (value >> amount, value & ((1 << amount) - 1), 1 << (amount - 1));
+        let round_up = match rounding {
+            Rounding::Truncate => false,
+            Rounding::Nearest => remainder >= half,
+            Rounding::NearestEven => {
+                remainder > half || (remainder == half && quotient & 1 == 1)
+            }
+        };
+        quotient + round_up as i128
+    }
+
+    /// `value` reduced modulo a non-zero `modulus` of at most 64 by binary
+    /// long division, without a division instruction.
+    pub const fn reduce(value: usize, modulus: usize) -> usize {
+        let (mut remainder, mut i) = (0, usize::BITS);
+        while i > 0 {
+            i -= 1;
+            remainder = (remainder << 1) | ((value >> i) & 1);
+            if remainder >= modulus {
+                remainder -= modulus;
+            }
+        }
+        remainder
+    }
+
+    /// Value of an ASCII digit in any radix up to 16, or `u8::MAX`.
+    #[cfg(feature = "fmt")]
+    pub const fn digit_value(byte: u8) -> u8 {
+        match byte {
+            b'0'..=b'9' => byte - b'0',
+            b'a'..=b'f' => byte - b'a' + 10,
+            b'A'..=b'F' => byte - b'A' + 10,
+            _ => u8::MAX,
+        }
+    }
+
+    /// Parse `bytes[start..]` as digits of `radix_bits` bits each.
+    #[cfg(feature = "fmt")]
+    pub const fn parse_digits(
+        bytes: &[u8],
+        start: usize,
+        radix_bits: usize,
+    ) -> Result<u64, Error> {
+        if start >= bytes.len() {
+            return Err(Error::InvalidFormat);
+        }
+        let (mut value, mut i) = (0u64, start);
+        while i < bytes.len() {
+            let digit = digit_value(bytes[i]);
+            if digit as usize >= 1 << radix_bits {
+                return Err(Error::InvalidDigit);
+            }
+            if (value >> (64 - radix_bits)) != 0 {
+                return Err(Error::Overflow);
+            }
+            value = (value << radix_bits) | digit as u64;
+            i += 1;
+        }
+        Ok(value)
+    }
+
+    /// Parse decimal digits at `bytes[start..]`, returning the value and the
+    /// index following the last digit.
+    #[cfg(feature = "fmt")]
+    pub const fn parse_decimal(
+        bytes: &[u8],
+        start: usize,
+    ) -> Result<(u64, usize), Error> {
+        let (mut value, mut i) = (0u64, start);
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            value = match value.checked_mul(10) {
+                Some(value) => {
+                    match value.checked_add((bytes[i] - b'0') as u64) {
+                        Some(value) => value,
+                        None => return Err(Error::InvalidLength),
+                    }
+                }
+                None => return Err(Error::InvalidLength),
+            };
+            i += 1;
+        }
+        match i == start {
+            true => Err(Error::InvalidFormat),
+            false => Ok((value, i)),
+        }
+    }
+
+    /// Write the lowest `count` digits of `value`, most significant first.
+    #[cfg(feature = "fmt")]
+    pub fn write_digits(
+        value: u64,
+        count: usize,
+        radix_bits: usize,
+        buf: &mut [u8],
+    ) -> Result<usize, Error> {
+        let buf = buf.get_mut(..count).ok_or(Error::BufferTooSmall)?;
+        for (i, byte) in buf.iter_mut().rev().enumerate() {
+            let digit = value.checked_shr((i * radix_bits) as u32).unwrap_or(0)
+                & ((1 << radix_bits) - 1);
+            *byte = b"0123456789abcdef"[digit as usize];
+        }
+        Ok(count)
+    }
+
+    /// Write `value` in decimal without leading zeros.
+    #[cfg(feature = "fmt")]
+    pub fn write_decimal(value: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        let mut count = 1;
+        while count < 20 && value >= 10u64.pow(count as u32) {
+            count += 1;
+        }
+        let buf = buf.get_mut(..count).ok_or(Error::BufferTooSmall)?;
+        for (i, byte) in buf.iter_mut().rev().enumerate() {
+            *byte = b'0' + (value / 10u64.pow(i as u32) % 10) as u8;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
     use paste::paste;
@@ -241,6 +1380,116 @@ mod tests {
                 assert_eq!(bv, NBV::zeros(NBV::BIT_SIZE));
             }
 
+            #[test]
+            fn [<test_nanobv_signed_value_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).signed_value(), -1);
+                assert_eq!(NBV::new(0b0111, 4).signed_value(), 7);
+                assert_eq!(NBV::new(0b1000, 4).signed_value(), -8);
+            }
+
+            #[test]
+            fn [<test_nanobv_size_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(size_of::<NBV>(), size_of::<[$type; 2]>());
+                assert_eq!(size_of::<Option<NBV>>(), size_of::<NBV>());
+            }
+
+            #[test]
+            fn [<test_nanobv_count_ones_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let data = rng.generate();
+                assert_eq!(NBV::new(data, NBV::BIT_SIZE).count_ones(), data.count_ones() as usize);
+                assert_eq!(NBV::new(0b1011, 6).count_ones(), 3);
+                assert_eq!(NBV::new(0b1011, 6).count_zeros(), 3);
+            }
+
+            #[test]
+            fn [<test_nanobv_predicates_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert!(NBV::ones(NBV::BIT_SIZE).all());
+                assert!(NBV::new(0b111, 3).all() && NBV::new(0b111, 3).any() && !NBV::new(0b111, 3).none());
+                assert!(!NBV::new(0b101, 3).all() && NBV::new(0b101, 3).any());
+                assert!(!NBV::zeros(3).all() && !NBV::zeros(3).any() && NBV::zeros(3).none());
+                assert!(NBV::new(0b101, 3).test_bit(2));
+                assert!(!NBV::new(0b101, 3).test_bit(1));
+            }
+
+            #[test]
+            fn [<test_nanobv_significant_bits_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::zeros(NBV::BIT_SIZE).significant_bits(), 0);
+                assert_eq!(NBV::new(1, 5).significant_bits(), 1);
+                assert_eq!(NBV::new(0b1_0110, 7).significant_bits(), 5);
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).significant_bits(), NBV::BIT_SIZE);
+            }
+
+            #[test]
+            fn [<test_nanobv_bmi_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b0101_1000, 8);
+                assert_eq!(bv.blsi(), NBV::new(0b0000_1000, 8));
+                assert_eq!(bv.blsr(), NBV::new(0b0101_0000, 8));
+                assert_eq!(bv.blsmsk(), NBV::new(0b0000_1111, 8));
+                assert_eq!(bv.bzhi(5), NBV::new(0b0001_1000, 8));
+                assert_eq!((bv.bzhi(0), bv.bzhi(8), bv.bzhi(100)), (NBV::zeros(8), bv, bv));
+                let zero = NBV::zeros(6);
+                assert_eq!((zero.blsi(), zero.blsr(), zero.blsmsk()), (zero, zero, NBV::ones(6)));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let mut bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let mut bits = bv.iter_set_bits();
+                while bv.any() {
+                    assert_eq!(bv.blsi(), NBV::zeros(NBV::BIT_SIZE).set_bit(bits.next().unwrap()));
+                    assert_eq!(bv.blsi() | bv.blsr(), bv);
+                    bv = bv.blsr();
+                }
+                assert_eq!(bits.next(), None);
+            }
+
+            #[test]
+            fn [<test_nanobv_const_cmp_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                const EQ: bool = NBV::new(5, 4).const_eq(NBV::new(5, 4));
+                assert!(EQ);
+                assert!(!NBV::new(5, 4).const_eq(NBV::new(5, 8)));
+                assert!(!NBV::new(5, 4).const_eq(NBV::new(6, 4)));
+                assert_eq!(NBV::new(5, 4).const_cmp(NBV::new(6, 3)), Ordering::Less);
+                assert_eq!(NBV::new(7, 3).const_cmp(NBV::new(6, 8)), Ordering::Greater);
+                assert_eq!(NBV::new(5, 4).const_cmp(NBV::new(5, 8)), Ordering::Less);
+                assert_eq!(NBV::new(5, 8).const_cmp(NBV::new(5, 4)), Ordering::Greater);
+                assert_eq!(NBV::new(5, 4).const_cmp(NBV::new(5, 4)), Ordering::Equal);
+            }
+
+            #[test]
+            fn [<test_nanobv_eq_value_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert!(NBV::new(5, 4).eq_value(NBV::new(5, 8)));
+                assert!(NBV::new(0xFF, 4).eq_value(NBV::new(0x0F, 8)));
+                assert!(!NBV::new(5, 4).eq_value(NBV::new(6, 4)));
+            }
+
+            #[test]
+            fn [<test_nanobv_try_new_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::try_new(0xFF, 4), Ok(NBV::new(0xF, 4)));
+                assert_eq!(NBV::try_new(1, NBV::BIT_SIZE), Ok(NBV::new(1, NBV::BIT_SIZE)));
+                assert_eq!(NBV::try_new(1, 0), Err(Error::InvalidLength));
+                assert_eq!(NBV::try_new(1, NBV::BIT_SIZE + 1), Err(Error::InvalidLength));
+            }
+
+            #[test]
+            fn [<test_nanobv_from_value_min_len_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::from_value_min_len(0), NBV::zeros(1));
+                assert_eq!(NBV::from_value_min_len(1), NBV::new(1, 1));
+                assert_eq!(NBV::from_value_min_len(0b101_1010), NBV::new(0b101_1010, 7));
+                assert_eq!(NBV::from_value_min_len($type::MAX), NBV::ones(NBV::BIT_SIZE));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::from_value_min_len(rng.generate());
+                assert_eq!(bv.len(), bv.significant_bits().max(1));
+            }
+
             #[test]
             fn [<test_nanobv_get_bit_ $type>]() {
                 type NBV = NanoBV::<$type>;
@@ -281,6 +1530,30 @@ mod tests {
                 assert_eq!(bv.get_bit(offset), value);
             }
 
+            #[test]
+            fn [<test_nanobv_msb0_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let offset = rng.generate_range(0, NBV::BIT_SIZE);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                assert_eq!(bv.get_bit_msb0(offset), bv.reverse().get_bit(offset));
+                assert_eq!(bv.set_bit_msb0(offset).reverse(), bv.reverse().set_bit(offset));
+                assert_eq!(bv.clear_bit_msb0(offset).reverse(), bv.reverse().clear_bit(offset));
+                assert_eq!(bv.assign_bit_msb0(1, offset).get_bit_msb0(offset), 1);
+                assert_eq!(NBV::zeros(5).set_bit_msb0(0), NBV::new(0b10000, 5));
+            }
+
+            #[test]
+            fn [<test_nanobv_iter_set_bits_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                assert_eq!(bv.iter_set_bits().len(), bv.value().count_ones() as usize);
+                assert_eq!(bv.iter_set_bits().fold(NBV::zeros(NBV::BIT_SIZE), |acc, offset| acc.set_bit(offset)), bv);
+                let mut bits = NBV::new(0b1001_0010, 8).iter_set_bits();
+                assert_eq!((bits.next(), bits.next(), bits.next(), bits.next()), (Some(1), Some(4), Some(7), None));
+            }
+
             #[test]
             fn [<test_nanobv_reverse_ $type>]() {
                 type NBV = NanoBV::<$type>;
@@ -289,9 +1562,614 @@ mod tests {
                 let bv = NBV::new(data, NBV::BIT_SIZE);
                 assert_eq!(bv.reverse().value(), data.reverse_bits());
             }
+
+            #[test]
+            fn [<test_nanobv_reverse_low_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0b1010_0011, 8).reverse_low(3), NBV::new(0b1010_0110, 8));
+                assert_eq!(NBV::new(0b1010_0011, 8).reverse_low(1), NBV::new(0b1010_0011, 8));
+                let addresses = [0, 4, 2, 6, 1, 5, 3, 7];
+                for (index, &address) in addresses.iter().enumerate() {
+                    assert_eq!(NBV::new(index as $type, 8).reverse_low(3), NBV::new(address, 8));
+                }
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                assert_eq!(bv.reverse_low(NBV::BIT_SIZE), bv.reverse());
+                assert_eq!(bv.reverse_low(5).reverse_low(5), bv);
+            }
+
+            #[test]
+            #[should_panic]
+            fn [<test_nanobv_reverse_low_invalid_ $type>]() {
+                NanoBV::<$type>::new(0b1010, 4).reverse_low(5);
+            }
+
+            #[test]
+            fn [<test_nanobv_reflect_bytes_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0b1100_0001, 8).reflect_bytes(), NBV::new(0b1000_0011, 8));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let reflected = bv.reflect_bytes();
+                assert_eq!(reflected.reflect_bytes(), bv);
+                assert_eq!(reflected.value().to_le_bytes().map(u8::reverse_bits), bv.value().to_le_bytes());
+                assert_eq!(reflected.reverse().value(), bv.value().swap_bytes());
+            }
+
+            #[test]
+            #[should_panic]
+            fn [<test_nanobv_reflect_bytes_invalid_ $type>]() {
+                NanoBV::<$type>::ones(4).reflect_bytes();
+            }
+
+            #[test]
+            fn [<test_nanobv_permute_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let mut table = [0u8; NBV::BIT_SIZE];
+                for (i, entry) in table.iter_mut().enumerate() {
+                    *entry = (NBV::BIT_SIZE - 1 - i) as u8;
+                }
+                assert_eq!(bv.permute(&table), Some(bv.reverse()));
+                table[0] = table[1];
+                assert_eq!(bv.permute(&table), None);
+                assert_eq!(bv.permute(&table[1..]), None);
+            }
+
+            #[test]
+            fn [<test_nanobv_gather_scatter_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1011_0010, 8);
+                let indices = [7, 1, 4, 4];
+                assert_eq!(bv.gather(&indices), NBV::new(0b1111, 4));
+                assert_eq!(bv.gather(&[0, 2, 3]), NBV::new(0b000, 3));
+                assert_eq!(NBV::zeros(8).scatter(&indices[..3], NBV::new(0b101, 3)), NBV::new(0b1001_0000, 8));
+                assert_eq!(bv.scatter(&[0, 7], NBV::new(0b01, 2)), NBV::new(0b0011_0011, 8));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let mut table = [0u8; NBV::BIT_SIZE];
+                for (i, entry) in table.iter_mut().enumerate() {
+                    *entry = (NBV::BIT_SIZE - 1 - i) as u8;
+                }
+                assert_eq!(bv.gather(&table), bv.reverse());
+                assert_eq!(NBV::zeros(NBV::BIT_SIZE).scatter(&table, bv.gather(&table)), bv);
+            }
+
+            #[test]
+            #[should_panic]
+            fn [<test_nanobv_gather_invalid_offset_ $type>]() {
+                NanoBV::<$type>::new(0b101, 3).gather(&[0, 3]);
+            }
+
+            #[test]
+            fn [<test_nanobv_map_lanes_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(<$type>::MAX / 0xFF * 0x12, NBV::BIT_SIZE);
+                assert_eq!(bv.map_bytes(|byte| byte.rotate_left(4)), NBV::new(<$type>::MAX / 0xFF * 0x21, NBV::BIT_SIZE));
+                assert_eq!(bv.map_nibbles(|nibble| nibble ^ 0xF), NBV::new(<$type>::MAX / 0xFF * 0xED, NBV::BIT_SIZE));
+                assert_eq!(bv.map_bytes(|byte| byte), bv);
+                assert_eq!(NBV::new(0b101_0011, 7).map_bytes(|byte| byte.wrapping_add(0x30)), NBV::new(0b000_0011, 7));
+                assert_eq!(NBV::new(0b10_0011, 6).map_nibbles(|nibble| nibble + 1), NBV::new(0b11_0100, 6));
+                assert_eq!(NBV::new(0b11, 2).map_nibbles(|nibble| nibble + 1), NBV::zeros(2));
+            }
+
+            #[test]
+            fn [<test_nanobv_shift_fill_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1001_0110, 8);
+                assert_eq!(bv.shl_fill(0, true), bv);
+                assert_eq!(bv.shl_fill(3, true), NBV::new(0b1011_0111, 8));
+                assert_eq!(bv.shl_fill(3, false), NBV::new(0b1011_0000, 8));
+                assert_eq!(bv.shr_fill(3, true), NBV::new(0b1111_0010, 8));
+                assert_eq!(bv.shr_fill(3, false), NBV::new(0b0001_0010, 8));
+                assert_eq!(bv.shl_fill(8, true), NBV::ones(8));
+                assert_eq!(bv.shr_fill(100, false), NBV::zeros(8));
+                let full = NBV::zeros(NBV::BIT_SIZE);
+                assert_eq!(full.shl_fill(NBV::BIT_SIZE - 1, true), NBV::new($type::MAX >> 1, NBV::BIT_SIZE));
+                assert_eq!(full.shr_fill(1, true), NBV::new(1 << (NBV::BIT_SIZE - 1), NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_insert_bit_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1011, 4);
+                assert_eq!(bv.insert_bit(2, 0), NBV::new(0b10011, 5));
+                assert_eq!(bv.insert_bit(0, 1), NBV::new(0b10111, 5));
+                assert_eq!(bv.insert_bit(4, 1), NBV::new(0b11011, 5));
+                let wide = NBV::ones(NBV::BIT_SIZE - 1).insert_bit((NBV::BIT_SIZE - 1) as $type, 0);
+                assert_eq!(wide, NBV::new($type::MAX >> 1, NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_remove_bit_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b10111, 5);
+                assert_eq!(bv.remove_bit(0), (1, NBV::new(0b1011, 4)));
+                assert_eq!(bv.remove_bit(3), (0, NBV::new(0b1111, 4)));
+                assert_eq!(bv.remove_bit(4), (1, NBV::new(0b0111, 4)));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let offset = rng.generate_range(0, NBV::BIT_SIZE) as $type;
+                let (bit, removed) = bv.remove_bit(offset);
+                assert_eq!(removed.insert_bit(offset, bit), bv);
+            }
+
+            #[test]
+            fn [<test_nanobv_compress_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b0100_1010, 8);
+                assert_eq!(bv.compress_low(), NBV::new(0b0000_0111, 8));
+                assert_eq!(bv.compress_high(), NBV::new(0b1110_0000, 8));
+                assert_eq!(NBV::zeros(8).compress_high(), NBV::zeros(8));
+                let full = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!((full.compress_low(), full.compress_high()), (full, full));
+            }
+
+            #[test]
+            fn [<test_nanobv_abs_diff_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(3, 4).abs_diff(NBV::new(10, 4)), NBV::new(7, 4));
+                assert_eq!(NBV::new(10, 4).abs_diff(NBV::new(3, 4)), NBV::new(7, 4));
+                assert_eq!(NBV::new(0xF3, 8).abs_diff(NBV::new(0x5, 4)), NBV::new(2, 4));
+                let max = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(NBV::zeros(NBV::BIT_SIZE).abs_diff(max), max);
+            }
+
+            #[test]
+            fn [<test_nanobv_midpoint_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                const MID: NBV = NBV::new(3, 4).midpoint(NBV::new(10, 4));
+                assert_eq!(MID, NBV::new(6, 4));
+                assert_eq!(NBV::new(10, 4).midpoint(NBV::new(4, 4)), NBV::new(7, 4));
+                assert_eq!(NBV::new(0xF3, 8).midpoint(NBV::new(0x5, 4)), NBV::new(4, 4));
+                let max = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(max.midpoint(max), max);
+                assert_eq!(max.midpoint(max.clear_bit(0)), max.clear_bit(0));
+            }
+
+            #[test]
+            fn [<test_nanobv_mod_inverse_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(3, 4).mod_inverse(), Some(NBV::new(11, 4)));
+                assert_eq!(NBV::new(4, 4).mod_inverse(), None);
+                const INVERSE: Option<NanoBV<$type>> = NBV::new(7, 8).mod_inverse();
+                assert_eq!(INVERSE, Some(NBV::new(183, 8)));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate() | 1, NBV::BIT_SIZE);
+                assert_eq!(bv.mod_inverse().map(|inverse| inverse.value().wrapping_mul(bv.value())), Some(1));
+            }
+
+            #[test]
+            fn [<test_nanobv_mac_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(5, 8).mac(NBV::new(3, 4), NBV::new(7, 4)), NBV::new(26, 8));
+                assert_eq!(NBV::new(5, 4).mac(NBV::new(3, 8), NBV::new(7, 8)), NBV::new(10, 4));
+                let max = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(max.mac(max, max), NBV::zeros(NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_dot_jaccard_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (lhs, rhs) = (NBV::new(0b1110_0110, 8), NBV::new(0b0110_0011, 8));
+                assert_eq!(lhs.dot(rhs), 3);
+                assert_eq!(lhs.jaccard(rhs), 0.5);
+                assert_eq!(lhs.dot(NBV::new(0b0110, 4)), 2);
+                assert_eq!(lhs.jaccard(NBV::new(0b0110, 4)), 1.0);
+                assert_eq!(NBV::zeros(8).jaccard(NBV::zeros(8)), 1.0);
+                assert_eq!(lhs.jaccard(lhs.bvxor(NBV::ones(8))), 0.0);
+            }
+
+            #[test]
+            fn [<test_nanobv_blend_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let blended = NBV::blend(NBV::new(0b1111_0000, 8), NBV::new(0b1010_1010, 8), NBV::new(0b0101_0101, 8));
+                assert_eq!(blended, NBV::new(0b1010_0101, 8));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let (a, b) = (NBV::new(rng.generate(), NBV::BIT_SIZE), NBV::new(rng.generate(), NBV::BIT_SIZE));
+                assert_eq!(NBV::blend(NBV::ones(NBV::BIT_SIZE), a, b), a);
+                assert_eq!(NBV::blend(NBV::zeros(NBV::BIT_SIZE), a, b), b);
+            }
+
+            #[test]
+            fn [<test_nanobv_matches_masked_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (value, care) = (NBV::new(0b1010_0000, 8), NBV::new(0b1111_0000, 8));
+                assert!(NBV::new(0b1010_0110, 8).matches_masked(value, care));
+                assert!(!NBV::new(0b1011_0110, 8).matches_masked(value, care));
+                assert!(NBV::new(0b0101_0101, 8).matches_masked(value, NBV::zeros(8)));
+                assert!(!NBV::new(0b1010_0001, 8).matches_masked(value, NBV::ones(8)));
+            }
+
+            #[test]
+            fn [<test_nanobv_one_hot_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::one_hot(3, 8), NBV::new(0b1000, 8));
+                assert_eq!(NBV::new(0b1000, 8).decode_one_hot(), Some(3));
+                assert_eq!(NBV::new(0b1010, 8).decode_one_hot(), None);
+                assert_eq!(NBV::zeros(8).decode_one_hot(), None);
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let index = rng.generate_range(0, NBV::BIT_SIZE) as $type;
+                assert_eq!(NBV::one_hot(index, NBV::BIT_SIZE).decode_one_hot(), Some(index));
+            }
+
+            #[test]
+            fn [<test_nanobv_thermometer_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::thermometer(3, 8), NBV::new(0b111, 8));
+                assert_eq!(NBV::thermometer(0, 8), NBV::zeros(8));
+                assert_eq!(NBV::thermometer(8, 8), NBV::ones(8));
+                assert_eq!(NBV::new(0b0111, 8).decode_thermometer(), Some(3));
+                assert_eq!(NBV::new(0b0110, 8).decode_thermometer(), None);
+                assert_eq!(NBV::zeros(8).decode_thermometer(), Some(0));
+                let full = NBV::thermometer(NBV::BIT_SIZE, NBV::BIT_SIZE);
+                assert_eq!(full.decode_thermometer(), Some(NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_leading_sign_bits_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0b0001_0000, 8).leading_sign_bits(), 2);
+                assert_eq!(NBV::new(0b1110_1111, 8).leading_sign_bits(), 2);
+                assert_eq!(NBV::new(0b0100_0000, 8).leading_sign_bits(), 0);
+                assert_eq!(NBV::zeros(8).leading_sign_bits(), 7);
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).leading_sign_bits(), NBV::BIT_SIZE - 1);
+                assert_eq!(NBV::new(1, 1).leading_sign_bits(), 0);
+            }
+
+            #[test]
+            fn [<test_nanobv_normalize_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0b0001_0110, 8).normalize(), Some((NBV::new(0b1011_0000, 8), 3)));
+                assert_eq!(NBV::new(0b100, 3).normalize(), Some((NBV::new(0b100, 3), 0)));
+                assert_eq!(NBV::new(1, NBV::BIT_SIZE).normalize(), Some((NBV::new(1 << (NBV::BIT_SIZE - 1), NBV::BIT_SIZE), NBV::BIT_SIZE - 1)));
+                assert_eq!(NBV::zeros(5).normalize(), None);
+            }
+
+            #[test]
+            fn [<test_nanobv_transitions_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0b0110_1000, 8).transitions(), 4);
+                assert_eq!(NBV::new(0b1000_0000, 8).transitions(), 1);
+                assert_eq!(NBV::new(0b0101, 4).transitions(), 3);
+                assert_eq!(NBV::new(1, 1).transitions(), 0);
+                assert_eq!(NBV::zeros(NBV::BIT_SIZE).transitions(), 0);
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).transitions(), 0);
+            }
+
+            #[test]
+            fn [<test_nanobv_is_palindrome_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert!(NBV::new(0b1001, 4).is_palindrome());
+                assert!(NBV::new(0b10101, 5).is_palindrome());
+                assert!(!NBV::new(0b1001, 5).is_palindrome());
+                assert!(NBV::new(1, 1).is_palindrome());
+                assert!(NBV::ones(NBV::BIT_SIZE).is_palindrome());
+                assert!(!NBV::new(1, NBV::BIT_SIZE).is_palindrome());
+            }
+
+            #[test]
+            fn [<test_nanobv_majority_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (a, b, c) = (NBV::new(0b1100, 4), NBV::new(0b1010, 4), NBV::new(0b0110, 4));
+                assert_eq!(NBV::majority(a, b, c), NBV::new(0b1110, 4));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let (good, bad) = (NBV::new(rng.generate(), NBV::BIT_SIZE), NBV::new(rng.generate(), NBV::BIT_SIZE));
+                assert_eq!(NBV::majority(good, bad, good), good);
+            }
+
+            #[test]
+            fn [<test_nanobv_next_with_same_popcount_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0b0011, 4).next_with_same_popcount(), Some(NBV::new(0b0101, 4)));
+                assert_eq!(NBV::new(0b0110, 4).next_with_same_popcount(), Some(NBV::new(0b1001, 4)));
+                assert_eq!(NBV::new(0b1100, 4).next_with_same_popcount(), None);
+                assert_eq!(NBV::zeros(4).next_with_same_popcount(), None);
+                let top = NBV::new(0b11 << (NBV::BIT_SIZE - 2), NBV::BIT_SIZE);
+                assert_eq!(top.next_with_same_popcount(), None);
+                let mut combinations = NBV::combinations(2, 4);
+                assert_eq!(combinations.next(), Some(NBV::new(0b0011, 4)));
+                assert_eq!(combinations.count(), 5);
+                assert_eq!(NBV::combinations(0, 4).count(), 1);
+                assert!(NBV::combinations(3, 8).all(|bv| bv.count_ones() == 3));
+                assert_eq!(NBV::combinations(3, 8).count(), 56);
+            }
+
+            #[test]
+            fn [<test_nanobv_all_values_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut values = NBV::all_values(2);
+                assert_eq!(values.next(), Some(NBV::new(0, 2)));
+                assert_eq!(values.next(), Some(NBV::new(1, 2)));
+                assert_eq!(values.next(), Some(NBV::new(2, 2)));
+                assert_eq!(values.next(), Some(NBV::new(3, 2)));
+                assert_eq!(values.next(), None);
+                assert_eq!(NBV::all_values(8).count(), 256);
+                assert_eq!(NBV::all_values(8).filter(|bv| bv.count_ones() == 3).count(), NBV::combinations(3, 8).count());
+            }
+
+            #[test]
+            fn [<test_nanobv_window_popcounts_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut counts = NBV::new(0b1101_0110, 8).window_popcounts(3);
+                assert_eq!(counts.len(), 6);
+                for expected in [2, 2, 2, 1, 2, 2] {
+                    assert_eq!(counts.next(), Some(expected));
+                }
+                assert_eq!(counts.next(), None);
+                assert_eq!(counts.len(), 0);
+                assert!(NBV::new(0b101, 3).window_popcounts(3).eq([2]));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                for (offset, count) in bv.window_popcounts(4).enumerate() {
+                    assert_eq!(count, bv.extract(offset + 3, offset).count_ones());
+                }
+                assert_eq!(bv.window_popcounts(1).sum::<usize>(), bv.count_ones());
+            }
+
+            #[test]
+            fn [<test_nanobv_every_nth_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1101_0110, 8);
+                assert_eq!(bv.every_nth(2, 0), NBV::new(0b1110, 4));
+                assert_eq!(bv.every_nth(2, 1), NBV::new(0b1001, 4));
+                assert_eq!(bv.every_nth(3, 2), NBV::new(0b01, 2));
+                assert_eq!(bv.every_nth(1, 0), bv);
+                assert_eq!(NBV::new(0b1100, 4).expand_every_nth(2, 0, 8), NBV::new(0b0101_0000, 8));
+                assert_eq!(NBV::new(0b11, 2).expand_every_nth(3, 2, 8), NBV::new(0b0010_0100, 8));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                for n in 1..=4 {
+                    let mut merged = NBV::zeros(bv.len());
+                    for phase in 0..n {
+                        merged |= bv.every_nth(n, phase).expand_every_nth(n, phase, bv.len());
+                    }
+                    assert_eq!(merged, bv);
+                }
+            }
+
+            #[test]
+            #[should_panic]
+            fn [<test_nanobv_every_nth_invalid_ $type>]() {
+                NanoBV::<$type>::new(0b1101_0110, 8).every_nth(2, 2);
+            }
+
+            #[test]
+            fn [<test_nanobv_spread_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0b1011, 4).spread2(), NBV::new(0b0100_0101, 8));
+                assert_eq!(NBV::new(0b0100_0101, 8).compact2(), NBV::new(0b1011, 4));
+                assert_eq!(NBV::new(0b11, 2).spread3(), NBV::new(0b00_1001, 6));
+                assert_eq!(NBV::new(0b00_1001, 6).compact3(), NBV::new(0b11, 2));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let (x, y, z) = (NBV::new(rng.generate(), 2), NBV::new(rng.generate(), 2), NBV::new(rng.generate(), 2));
+                let morton = x.spread3() | y.expand_every_nth(3, 1, 6) | z.expand_every_nth(3, 2, 6);
+                assert_eq!((morton.compact3(), morton.every_nth(3, 1), morton.every_nth(3, 2)), (x, y, z));
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE / 2);
+                assert_eq!(bv.spread2().compact2(), bv);
+                assert_eq!((bv.spread2() | bv.spread2().shl_fill(1, false)).count_ones(), 2 * bv.count_ones());
+            }
+
+            #[test]
+            #[should_panic]
+            fn [<test_nanobv_spread_invalid_ $type>]() {
+                NanoBV::<$type>::ones(NanoBV::<$type>::BIT_SIZE / 2 + 1).spread2();
+            }
+
+            #[test]
+            fn [<test_nanobv_prefix_scan_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b0010_0110, 8);
+                assert_eq!(bv.prefix_xor(), NBV::new(0b1110_0010, 8));
+                assert_eq!(bv.prefix_or(), NBV::new(0b1111_1110, 8));
+                assert_eq!(bv.prefix_and(), NBV::new(0b0000_0000, 8));
+                assert_eq!(NBV::new(0b1011_0111, 8).prefix_and(), NBV::new(0b0000_0111, 8));
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).prefix_and(), NBV::ones(NBV::BIT_SIZE));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let (xor, or, and) = (bv.prefix_xor(), bv.prefix_or(), bv.prefix_and());
+                let (mut running_xor, mut running_or, mut running_and) = (0, 0, 1);
+                for i in 0..bv.len() as $type {
+                    running_xor ^= bv.get_bit(i);
+                    running_or |= bv.get_bit(i);
+                    running_and &= bv.get_bit(i);
+                    assert_eq!((xor.get_bit(i), or.get_bit(i), and.get_bit(i)), (running_xor, running_or, running_and));
+                }
+            }
+
+            #[test]
+            fn [<test_nanobv_windows_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut windows = NBV::new(0b1101_0110, 8).windows(3);
+                assert_eq!(windows.len(), 6);
+                for expected in [0b110, 0b011, 0b101, 0b010, 0b101, 0b110] {
+                    assert_eq!(windows.next(), Some(NBV::new(expected, 3)));
+                }
+                assert_eq!(windows.next(), None);
+                assert_eq!(windows.len(), 0);
+                assert!(NBV::new(0b101, 3).windows(3).eq([NBV::new(0b101, 3)]));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                for (offset, window) in bv.windows(4).enumerate() {
+                    assert_eq!(window, bv.extract(offset + 3, offset));
+                }
+                assert!(bv.windows(5).map(|window| window.count_ones()).eq(bv.window_popcounts(5)));
+            }
+
+            #[test]
+            #[should_panic]
+            fn [<test_nanobv_windows_invalid_ $type>]() {
+                NanoBV::<$type>::new(0b101, 3).windows(4);
+            }
+
+            #[test]
+            fn [<test_nanobv_resize_saturating_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0x1F, 8).resize_saturating(4), NBV::new(0xF, 4));
+                assert_eq!(NBV::new(0x0A, 8).resize_saturating(4), NBV::new(0xA, 4));
+                assert_eq!(NBV::new(0xA, 4).resize_saturating(8), NBV::new(0xA, 8));
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).resize_saturating(1), NBV::new(1, 1));
+            }
+
+            #[test]
+            fn [<test_nanobv_shr_round_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (six, ten, eleven) = (NBV::new(0b0110, 8), NBV::new(0b1010, 8), NBV::new(0b1011, 8));
+                assert_eq!(eleven.shr_round(2, Rounding::Truncate), NBV::new(2, 8));
+                assert_eq!(eleven.shr_round(2, Rounding::Nearest), NBV::new(3, 8));
+                assert_eq!(ten.shr_round(2, Rounding::Nearest), NBV::new(3, 8));
+                assert_eq!(ten.shr_round(2, Rounding::NearestEven), NBV::new(2, 8));
+                assert_eq!(six.shr_round(2, Rounding::NearestEven), NBV::new(2, 8));
+                assert_eq!(ten.shr_round(0, Rounding::Nearest), ten);
+                assert_eq!(NBV::new(0xFF, 8).shr_round(8, Rounding::Nearest), NBV::new(1, 8));
+                assert_eq!(NBV::new(0x80, 8).shr_round(8, Rounding::NearestEven), NBV::new(0, 8));
+                let max = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(max.shr_round(NBV::BIT_SIZE, Rounding::Nearest), NBV::new(1, NBV::BIT_SIZE));
+                assert_eq!(max.shr_round(100, Rounding::Nearest), NBV::zeros(NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_align_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::new(0x13, 8).align_up(0x10), Some(NBV::new(0x20, 8)));
+                assert_eq!(NBV::new(0x20, 8).align_up(0x10), Some(NBV::new(0x20, 8)));
+                assert_eq!(NBV::new(0xF1, 8).align_up(0x10), None);
+                assert_eq!(NBV::new(0x13, 8).align_down(0x10), NBV::new(0x10, 8));
+                assert_eq!(NBV::new(0x13, 8).align_down(1), NBV::new(0x13, 8));
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).align_up(2), None);
+            }
+
+            #[test]
+            fn [<test_nanobv_prefix_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::prefix_mask(3, 8), NBV::new(0b1110_0000, 8));
+                assert_eq!(NBV::prefix_mask(0, 8), NBV::zeros(8));
+                assert_eq!(NBV::prefix_mask(NBV::BIT_SIZE, NBV::BIT_SIZE), NBV::ones(NBV::BIT_SIZE));
+                assert_eq!(NBV::new(0b1011_0000, 8).common_prefix_len(NBV::new(0b1010_1111, 8)), 3);
+                assert_eq!(NBV::new(0b1011_0000, 8).common_prefix_len(NBV::new(0b0011_0000, 8)), 0);
+                assert_eq!(NBV::new(0b1011, 4).common_prefix_len(NBV::new(0b1011, 4)), 4);
+            }
+
+            #[test]
+            #[cfg(feature = "fmt")]
+            fn [<test_nanobv_write_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut buf = [0; 64];
+                let bv = NBV::new(0b110_1010, 7);
+                assert_eq!(bv.write_binary(&mut buf), Ok(7));
+                assert_eq!(&buf[..7], b"1101010");
+                assert_eq!(bv.write_hex(&mut buf), Ok(2));
+                assert_eq!(&buf[..2], b"6a");
+                assert_eq!(NBV::new(0b101, 3).write_binary(&mut buf), Ok(3));
+                assert_eq!(&buf[..3], b"101");
+                assert_eq!(bv.write_binary(&mut buf[..6]), Err(Error::BufferTooSmall));
+                assert_eq!(bv.write_hex(&mut buf[..1]), Err(Error::BufferTooSmall));
+                let ones = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(ones.write_binary(&mut buf), Ok(NBV::BIT_SIZE));
+                assert!(buf[..NBV::BIT_SIZE].iter().all(|&digit| digit == b'1'));
+                assert_eq!(ones.write_hex(&mut buf), Ok(NBV::BIT_SIZE / 4));
+                assert!(buf[..NBV::BIT_SIZE / 4].iter().all(|&digit| digit == b'f'));
+            }
+
+            #[test]
+            fn [<test_nanobv_ops_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let lhs = NBV::new(rng.generate(), NBV::BIT_SIZE - 1);
+                let rhs = NBV::new(rng.generate() | 1, NBV::BIT_SIZE / 2);
+                let shift = NBV::new(3, NBV::BIT_SIZE);
+                assert_eq!(lhs & rhs, lhs.bvand(rhs));
+                assert_eq!(lhs | rhs, lhs.bvor(rhs));
+                assert_eq!(lhs ^ rhs, lhs.bvxor(rhs));
+                #[cfg(feature = "div")]
+                assert_eq!(lhs / rhs, lhs.bvdiv(rhs));
+                #[cfg(feature = "div")]
+                assert_eq!(lhs % rhs, lhs.bvrem(rhs));
+                assert_eq!(lhs << shift, lhs.bvshl(shift));
+                assert_eq!(lhs >> shift, lhs.bvshr(shift));
+                assert_eq!(NBV::new(0b1111, 4) + NBV::new(1, 4), NBV::zeros(4));
+                assert_eq!(NBV::new(0b1111, 4) * NBV::new(0b11, 4), NBV::new(0b1101, 4));
+                assert_eq!(NBV::new(0b1111, 4) - NBV::new(0b11, 2), NBV::new(0b00, 2));
+            }
+
+            #[test]
+            fn [<test_nanobv_mut_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let offset = rng.generate_range(0, NBV::BIT_SIZE);
+                let data = rng.generate();
+                let mut bv = NBV::default();
+                bv.set_value_mut(data);
+                assert_eq!(bv, NBV::new(data, NBV::BIT_SIZE));
+                bv.set_bit_mut(offset);
+                assert_eq!(bv.get_bit(offset), 1);
+                bv.clear_bit_mut(offset);
+                assert_eq!(bv.get_bit(offset), 0);
+                bv.assign_bit_mut(1, offset);
+                assert_eq!(bv.get_bit(offset), 1);
+                bv.reverse_mut();
+                assert_eq!(bv.value(), (data | (1 << offset)).reverse_bits());
+                bv.set_mut();
+                assert_eq!(bv, NBV::ones(NBV::BIT_SIZE));
+                bv.clear_mut();
+                assert_eq!(bv, NBV::zeros(NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_ops_assign_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut bv = NBV::new(0b1010, 4);
+                bv |= NBV::new(0b0101, 4);
+                assert_eq!(bv, NBV::ones(4));
+                bv &= NBV::new(0b0110, 4);
+                bv ^= NBV::new(0b0011, 4);
+                bv += NBV::new(1, 4);
+                bv <<= NBV::new(1, 4);
+                bv -= NBV::new(2, 4);
+                assert_eq!(bv, NBV::new(0b1010, 4));
+            }
+
+            #[cfg(feature = "const_trait_impl")]
+            #[test]
+            fn [<test_nanobv_const_ops_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                const BV: NBV = {
+                    let mut bv = NBV::new(0b1010, 4) | NBV::new(0b0101, 4);
+                    bv ^= NBV::new(0b0011, 4);
+                    bv
+                };
+                assert_eq!(BV, NBV::new(0b1100, 4));
+            }
         }
         };
     }
 
     ImplNanoBVTest!(for u8, u16, u32, u64);
+
+    macro_rules! ImplNanoBVMixedTest {
+        (for $(($narrow:ident, $wide:ident)),+) => {
+            $(ImplNanoBVMixedTest!($narrow, $wide);)*
+        };
+
+        ($narrow:ident, $wide:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_mixed_ops_ $narrow _ $wide>]() {
+                let mut rng = RNG::<WyRand, $narrow>::new($narrow::MAX as _);
+                let narrow = NanoBV::<$narrow>::new(rng.generate(), NanoBV::<$narrow>::BIT_SIZE);
+                let widened = NanoBV::<$wide>::from(narrow);
+                assert_eq!((widened.value(), widened.len()), (narrow.value() as $wide, narrow.len()));
+                let wide = NanoBV::<$wide>::ones(NanoBV::<$wide>::BIT_SIZE);
+                assert_eq!(wide & narrow, widened);
+                assert_eq!(narrow ^ wide, NanoBV::<$wide>::new(!narrow.value() as $wide, narrow.len()));
+                let mut bv = NanoBV::<$wide>::new(0b1010, 4);
+                bv |= NanoBV::<$narrow>::new(0b0101, 8);
+                assert_eq!(bv, NanoBV::<$wide>::ones(4));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVMixedTest!(for (u8, u16), (u8, u32), (u8, u64), (u16, u32), (u16, u64), (u32, u64));
 }
@@ -0,0 +1,156 @@
+//! Manchester and NRZI line coding.
+//!
+//! Bits are transmitted most significant bit first, and the line levels are
+//! returned in the same order: the first level sent is the most significant
+//! bit of the result.
+
+use crate::NanoBV;
+
+/// Manchester convention, naming the levels sent for each data bit.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum Manchester {
+    /// IEEE 802.3: a 0 is sent as `10` and a 1 as `01`.
+    Ieee,
+    /// G. E. Thomas: a 0 is sent as `01` and a 1 as `10`.
+    Thomas,
+}
+
+/// NRZI convention, naming the data bit sent as a level transition.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum Nrzi {
+    /// NRZ-M: a 1 toggles the level.
+    Mark,
+    /// NRZ-S: a 0 toggles the level, as in USB.
+    Space,
+}
+
+impl Manchester {
+    /// Retrieve the pair of levels sent for `bit`, first level high.
+    const fn pair(self, bit: bool) -> u8 {
+        match (self, bit) {
+            (Manchester::Ieee, false) | (Manchester::Thomas, true) => 0b10,
+            (Manchester::Ieee, true) | (Manchester::Thomas, false) => 0b01,
+        }
+    }
+}
+
+impl Nrzi {
+    /// Retrieve the data bit sent as a transition.
+    const fn toggle(self) -> bool {
+        match self {
+            Nrzi::Mark => true,
+            Nrzi::Space => false,
+        }
+    }
+}
+
+macro_rules! ImplNanoBVLine {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVLine!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Manchester encode, doubling the length.
+            pub const fn manchester_encode(&self, convention: Manchester) -> Self {
+                ["Invalid length provided."][(2 * self.len() > Self::BIT_SIZE) as usize];
+                let (mut encoded, mut i) = (0 as $type, 0);
+                while i < self.len() {
+                    encoded |= (convention.pair((self.data >> i) & 1 == 1) as $type) << (2 * i);
+                    i += 1;
+                }
+                NanoBV::<$type>::new(encoded, 2 * self.len())
+            }
+
+            /// Manchester decode, halving the length, or `None` if a pair of
+            /// levels has no transition.
+            pub const fn manchester_decode(&self, convention: Manchester) -> Option<Self> {
+                ["Invalid length provided."][(self.len() % 2 != 0) as usize];
+                let (mut decoded, mut i) = (0 as $type, 0);
+                while i < self.len() / 2 {
+                    let pair = ((self.data >> (2 * i)) & 0b11) as u8;
+                    match pair {
+                    _ if pair == convention.pair(true) => decoded |= 1 << i,
+                    _ if pair == convention.pair(false) => (),
+                    _ => return None,
+                    }
+                    i += 1;
+                }
+                Some(NanoBV::<$type>::new(decoded, self.len() / 2))
+            }
+
+            /// NRZI encode, starting from the line level `level`.
+            pub const fn nrzi_encode(&self, convention: Nrzi, level: bool) -> Self {
+                let (mut encoded, mut level, mut i) = (0 as $type, level, self.len());
+                while i > 0 {
+                    i -= 1;
+                    level ^= ((self.data >> i) & 1 == 1) == convention.toggle();
+                    encoded |= (level as $type) << i;
+                }
+                NanoBV::<$type>::new(encoded, self.len())
+            }
+
+            /// NRZI decode, given the line level `level` before the first bit.
+            pub const fn nrzi_decode(&self, convention: Nrzi, level: bool) -> Self {
+                let (mut decoded, mut level, mut i) = (0 as $type, level, self.len());
+                while i > 0 {
+                    i -= 1;
+                    let current = (self.data >> i) & 1 == 1;
+                    decoded |= (((current != level) == convention.toggle()) as $type) << i;
+                    level = current;
+                }
+                NanoBV::<$type>::new(decoded, self.len())
+            }
+        }
+    };
+}
+
+ImplNanoBVLine!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVLineTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVLineTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_manchester_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1011, 4);
+                assert_eq!(bv.manchester_encode(Manchester::Ieee), NBV::new(0b0110_0101, 8));
+                assert_eq!(bv.manchester_encode(Manchester::Thomas), NBV::new(0b1001_1010, 8));
+                assert_eq!(NBV::new(0b0110_0101, 8).manchester_decode(Manchester::Ieee), Some(bv));
+                assert_eq!(NBV::new(0b0110_0101, 8).manchester_decode(Manchester::Thomas), Some(NBV::new(0b0100, 4)));
+                assert_eq!(NBV::new(0b0110_0111, 8).manchester_decode(Manchester::Ieee), None);
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE / 2);
+                assert_eq!(bv.manchester_encode(Manchester::Ieee).manchester_decode(Manchester::Ieee), Some(bv));
+                assert_eq!(bv.manchester_encode(Manchester::Ieee).count_ones(), bv.len());
+            }
+
+            #[test]
+            fn [<test_nanobv_nrzi_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1011_0001, 8);
+                assert_eq!(bv.nrzi_encode(Nrzi::Mark, false), NBV::new(0b1101_1110, 8));
+                assert_eq!(bv.nrzi_encode(Nrzi::Mark, true), NBV::new(0b0010_0001, 8));
+                assert_eq!(bv.nrzi_encode(Nrzi::Space, true), NBV::new(0b1000_1011, 8));
+                assert_eq!(NBV::new(0b1101_1110, 8).nrzi_decode(Nrzi::Mark, false), bv);
+                assert_eq!(NBV::new(0b1000_1011, 8).nrzi_decode(Nrzi::Space, true), bv);
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                assert_eq!(bv.nrzi_encode(Nrzi::Space, false).nrzi_decode(Nrzi::Space, false), bv);
+            }
+        }
+        };
+    }
+
+    ImplNanoBVLineTest!(for u8, u16, u32, u64);
+}
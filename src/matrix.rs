@@ -0,0 +1,80 @@
+//! Bit-matrix transposes.
+//!
+//! A NanoBV is read as a square matrix with row `i` in the `i`th group of
+//! bits from the least significant end, so bit `n * i + j` holds row `i`,
+//! column `j`.
+
+use crate::NanoBV;
+
+impl NanoBV<u16> {
+    /// Transpose the NanoBV as a 4×4 bit matrix.
+    pub const fn transpose4x4(&self) -> Self {
+        assert!(self.len() == 16, "Invalid length provided.");
+        let mut x = self.data;
+        let t = (x ^ (x >> 3)) & 0x0A0A;
+        x ^= t ^ (t << 3);
+        let t = (x ^ (x >> 6)) & 0x00CC;
+        x ^= t ^ (t << 6);
+        NanoBV::<u16>::new(x, 16)
+    }
+}
+
+impl NanoBV<u64> {
+    /// Transpose the NanoBV as an 8×8 bit matrix.
+    pub const fn transpose8x8(&self) -> Self {
+        assert!(self.len() == 64, "Invalid length provided.");
+        let mut x = self.data;
+        let t = (x ^ (x >> 7)) & 0x00AA_00AA_00AA_00AA;
+        x ^= t ^ (t << 7);
+        let t = (x ^ (x >> 14)) & 0x0000_CCCC_0000_CCCC;
+        x ^= t ^ (t << 14);
+        let t = (x ^ (x >> 28)) & 0x0000_0000_F0F0_F0F0;
+        x ^= t ^ (t << 28);
+        NanoBV::<u64>::new(x, 64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    fn transpose(value: u64, n: usize) -> u64 {
+        let mut transposed = 0;
+        for i in 0..n {
+            for j in 0..n {
+                transposed |= ((value >> (n * i + j)) & 1) << (n * j + i);
+            }
+        }
+        transposed
+    }
+
+    #[test]
+    fn test_nanobv_transpose4x4() {
+        let bv = NanoBV::<u16>::new(0b0000_0000_0000_1111, 16);
+        assert_eq!(
+            bv.transpose4x4(),
+            NanoBV::<u16>::new(0b0001_0001_0001_0001, 16)
+        );
+        let mut rng = RNG::<WyRand, u16>::new(u16::MAX as _);
+        let bv = NanoBV::<u16>::new(rng.generate(), 16);
+        assert_eq!(
+            bv.transpose4x4().value() as u64,
+            transpose(bv.value() as u64, 4)
+        );
+        assert_eq!(bv.transpose4x4().transpose4x4(), bv);
+    }
+
+    #[test]
+    fn test_nanobv_transpose8x8() {
+        let bv = NanoBV::<u64>::new(0xFF, 64);
+        assert_eq!(
+            bv.transpose8x8(),
+            NanoBV::<u64>::new(0x0101_0101_0101_0101, 64)
+        );
+        let mut rng = RNG::<WyRand, u64>::new(u64::MAX as _);
+        let bv = NanoBV::<u64>::new(rng.generate(), 64);
+        assert_eq!(bv.transpose8x8().value(), transpose(bv.value(), 8));
+        assert_eq!(bv.transpose8x8().transpose8x8(), bv);
+    }
+}
@@ -0,0 +1,165 @@
+//! [`num-traits`](https://docs.rs/num-traits) implementations.
+//!
+//! Constants such as [`Zero::zero`] and [`Bounded::max_value`] take the full
+//! length of the backing type. The checked, wrapping and saturating traits
+//! forward to the methods of [`crate::checked`], so overflow is handled at the
+//! shorter length rather than at the size of the backing type.
+
+use crate::NanoBV;
+use num_traits::{
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub, One,
+    SaturatingAdd, SaturatingSub, WrappingAdd, WrappingMul, WrappingSub, Zero,
+};
+
+macro_rules! ImplNanoBVNum {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVNum!($type);)*
+    };
+
+    ($type:ident) => {
+        impl Zero for NanoBV<$type> {
+            fn zero() -> Self {
+                NanoBV::<$type>::zeros(Self::BIT_SIZE)
+            }
+
+            fn is_zero(&self) -> bool {
+                self.data == 0
+            }
+        }
+
+        impl One for NanoBV<$type> {
+            fn one() -> Self {
+                NanoBV::<$type>::new(1, Self::BIT_SIZE)
+            }
+        }
+
+        impl Bounded for NanoBV<$type> {
+            fn min_value() -> Self {
+                NanoBV::<$type>::zeros(Self::BIT_SIZE)
+            }
+
+            fn max_value() -> Self {
+                NanoBV::<$type>::ones(Self::BIT_SIZE)
+            }
+        }
+
+        impl CheckedAdd for NanoBV<$type> {
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                NanoBV::<$type>::checked_add(self, *rhs)
+            }
+        }
+
+        impl CheckedSub for NanoBV<$type> {
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                NanoBV::<$type>::checked_sub(self, *rhs)
+            }
+        }
+
+        impl CheckedMul for NanoBV<$type> {
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                NanoBV::<$type>::checked_mul(self, *rhs)
+            }
+        }
+
+        impl CheckedDiv for NanoBV<$type> {
+            fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                NanoBV::<$type>::checked_div(self, *rhs)
+            }
+        }
+
+        impl CheckedRem for NanoBV<$type> {
+            fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+                NanoBV::<$type>::checked_rem(self, *rhs)
+            }
+        }
+
+        impl WrappingAdd for NanoBV<$type> {
+            fn wrapping_add(&self, rhs: &Self) -> Self {
+                NanoBV::<$type>::wrapping_add(self, *rhs)
+            }
+        }
+
+        impl WrappingSub for NanoBV<$type> {
+            fn wrapping_sub(&self, rhs: &Self) -> Self {
+                NanoBV::<$type>::wrapping_sub(self, *rhs)
+            }
+        }
+
+        impl WrappingMul for NanoBV<$type> {
+            fn wrapping_mul(&self, rhs: &Self) -> Self {
+                NanoBV::<$type>::wrapping_mul(self, *rhs)
+            }
+        }
+
+        impl SaturatingAdd for NanoBV<$type> {
+            fn saturating_add(&self, rhs: &Self) -> Self {
+                NanoBV::<$type>::saturating_add(self, *rhs)
+            }
+        }
+
+        impl SaturatingSub for NanoBV<$type> {
+            fn saturating_sub(&self, rhs: &Self) -> Self {
+                NanoBV::<$type>::saturating_sub(self, *rhs)
+            }
+        }
+    };
+}
+
+ImplNanoBVNum!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    fn sum<T: Zero + CheckedAdd + Copy>(values: &[T]) -> Option<T> {
+        values.iter().try_fold(T::zero(), |acc, value| acc.checked_add(value))
+    }
+
+    macro_rules! ImplNanoBVNumTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVNumTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_num_constants_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::zero(), NBV::zeros(NBV::BIT_SIZE));
+                assert!(NBV::zero().is_zero());
+                assert!(NBV::new(0, 3).is_zero());
+                assert_eq!(NBV::one(), NBV::new(1, NBV::BIT_SIZE));
+                assert_eq!(NBV::min_value(), NBV::zeros(NBV::BIT_SIZE));
+                assert_eq!(NBV::max_value(), NBV::new($type::MAX, NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_num_arithmetic_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (a, b) = (NBV::new(0b1100, 4), NBV::new(0b0101, 4));
+                assert_eq!(CheckedAdd::checked_add(&a, &b), None);
+                assert_eq!(CheckedAdd::checked_add(&b, &b), Some(NBV::new(0b1010, 4)));
+                assert_eq!(CheckedSub::checked_sub(&a, &b), Some(NBV::new(0b0111, 4)));
+                assert_eq!(CheckedSub::checked_sub(&b, &a), None);
+                assert_eq!(CheckedMul::checked_mul(&NBV::new(3, 4), &b), Some(NBV::new(15, 4)));
+                assert_eq!(CheckedMul::checked_mul(&a, &b), None);
+                assert_eq!(CheckedDiv::checked_div(&a, &b), Some(NBV::new(2, 4)));
+                assert_eq!(CheckedDiv::checked_div(&a, &NBV::zeros(4)), None);
+                assert_eq!(CheckedRem::checked_rem(&a, &b), Some(NBV::new(2, 4)));
+                assert_eq!(CheckedRem::checked_rem(&a, &NBV::zeros(4)), None);
+                assert_eq!(WrappingAdd::wrapping_add(&a, &b), NBV::new(0b0001, 4));
+                assert_eq!(WrappingSub::wrapping_sub(&b, &a), NBV::new(0b1001, 4));
+                assert_eq!(WrappingMul::wrapping_mul(&a, &b), NBV::new(0b1100, 4));
+                assert_eq!(SaturatingAdd::saturating_add(&a, &b), NBV::ones(4));
+                assert_eq!(SaturatingSub::saturating_sub(&b, &a), NBV::zeros(4));
+                assert_eq!(WrappingAdd::wrapping_add(&NBV::max_value(), &NBV::one()), NBV::zero());
+                assert_eq!(CheckedAdd::checked_add(&NBV::max_value(), &NBV::new(1, 2)), None);
+                assert_eq!(sum(&[NBV::new(1, 8), NBV::new(2, 8)]), Some(NBV::new(3, 8)));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVNumTest!(for u8, u16, u32, u64);
+}
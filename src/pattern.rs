@@ -0,0 +1,134 @@
+//! Match patterns with don't-care bits.
+//!
+//! ```
+//! use nanobv::{pattern::Pattern, NanoBV};
+//!
+//! const LOAD: Pattern<u8> = match Pattern::<u8>::parse("10xx_01x1") {
+//!     Ok(pattern) => pattern,
+//!     Err(_) => panic!(),
+//! };
+//!
+//! assert!(LOAD.matches(NanoBV::<u8>::new(0b1011_0111, 8)));
+//! assert!(!LOAD.matches(NanoBV::<u8>::new(0b1111_0111, 8)));
+//! ```
+
+use crate::{Error, NanoBV};
+
+/// Bit pattern matching a [`NanoBV`] on the bits set in its care mask.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Pattern<T = u32> {
+    value: NanoBV<T>,
+    care: NanoBV<T>,
+}
+
+impl<T: Copy> Pattern<T> {
+    /// Retrieve the expected value of the cared bits.
+    pub const fn value(&self) -> NanoBV<T> {
+        self.value
+    }
+
+    /// Retrieve the mask of the cared bits.
+    pub const fn care(&self) -> NanoBV<T> {
+        self.care
+    }
+}
+
+macro_rules! ImplPattern {
+    (for $($type:tt),+) => {
+        $(ImplPattern!($type);)*
+    };
+
+    ($type:ident) => {
+        impl Pattern<$type> {
+            /// Create a new [`Pattern`] from the expected value and the care mask.
+            ///
+            /// Bits of `value` outside of `care` are cleared.
+            pub const fn new(value: NanoBV<$type>, care: NanoBV<$type>) -> Self {
+                ["Invalid length provided."][(value.len() != care.len()) as usize];
+                Pattern { value: value.bvand(care), care }
+            }
+
+            /// Parse a pattern such as `10xx_01x1`, most significant bit first.
+            ///
+            /// `x`, `X` and `-` are don't-care bits and `_` separators are
+            /// ignored. The number of bits is taken as the length.
+            pub const fn parse(pattern: &str) -> Result<Self, Error> {
+                let bytes = pattern.as_bytes();
+                let (mut value, mut care, mut length, mut i) = (0 as $type, 0 as $type, 0, 0);
+                while i < bytes.len() {
+                    let (bit, cared) = match bytes[i] {
+                    b'_' => {
+                        i += 1;
+                        continue;
+                    }
+                    b'0' => (0, 1),
+                    b'1' => (1, 1),
+                    b'x' | b'X' | b'-' => (0, 0),
+                    _ => return Err(Error::InvalidDigit),
+                    };
+                    if length == NanoBV::<$type>::BIT_SIZE {
+                        return Err(Error::InvalidLength);
+                    }
+                    value = (value << 1) | bit;
+                    care = (care << 1) | cared;
+                    length += 1;
+                    i += 1;
+                }
+                match length {
+                0 => Err(Error::InvalidLength),
+                _ => Ok(Pattern { value: NanoBV::<$type>::new(value, length), care: NanoBV::<$type>::new(care, length) }),
+                }
+            }
+
+            /// Check whether `bv` matches the pattern.
+            pub const fn matches(&self, bv: NanoBV<$type>) -> bool {
+                bv.matches_masked(self.value, self.care)
+            }
+        }
+    };
+}
+
+ImplPattern!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplPatternTest {
+        (for $($type:tt),+) => {
+            $(ImplPatternTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_pattern_parse_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let pattern = Pattern::<$type>::parse("10xx_01X1").unwrap();
+                assert_eq!(pattern.value(), NBV::new(0b1000_0101, 8));
+                assert_eq!(pattern.care(), NBV::new(0b1100_1101, 8));
+                assert_eq!(pattern, Pattern::<$type>::new(NBV::new(0b1011_0111, 8), NBV::new(0b1100_1101, 8)));
+                assert_eq!(Pattern::<$type>::parse("1-"), Ok(Pattern::<$type>::new(NBV::new(0b10, 2), NBV::new(0b10, 2))));
+                assert_eq!(Pattern::<$type>::parse("10z1"), Err(Error::InvalidDigit));
+                assert_eq!(Pattern::<$type>::parse("__"), Err(Error::InvalidLength));
+                let buf = [b'x'; 65];
+                let literal = core::str::from_utf8(&buf[..NBV::BIT_SIZE + 1]).unwrap();
+                assert_eq!(Pattern::<$type>::parse(literal), Err(Error::InvalidLength));
+            }
+
+            #[test]
+            fn [<test_pattern_matches_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let pattern = Pattern::<$type>::parse("1x0x").unwrap();
+                assert!(pattern.matches(NBV::new(0b1000, 4)));
+                assert!(pattern.matches(NBV::new(0b1101, 4)));
+                assert!(!pattern.matches(NBV::new(0b0101, 4)));
+                assert!(!pattern.matches(NBV::new(0b1110, 4)));
+            }
+        }
+        };
+    }
+
+    ImplPatternTest!(for u8, u16, u32, u64);
+}
@@ -0,0 +1,103 @@
+//! [Kani](https://model-checking.github.io/kani/) proof harnesses.
+//!
+//! The harnesses check, for every value and length of each backing type, that
+//! the stored data never exceeds the declared length, that the results of the
+//! operators stay within the shorter length, and that
+//! [`reverse`](NanoBV::reverse) is an involution. Addition, subtraction and
+//! multiplication are only checked where the backing type does not overflow,
+//! as Kani reports the overflow itself otherwise. The harnesses are compiled
+//! only when `cfg(kani)` is set, which `cargo kani` does:
+//!
+//! ```text
+//! cargo kani
+//! ```
+
+use crate::NanoBV;
+
+macro_rules! ImplNanoBVProofs {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVProofs!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Create a NanoBV of any value and any valid length.
+            fn proof_any() -> Self {
+                let length: usize = kani::any();
+                kani::assume(length >= 1 && length <= Self::BIT_SIZE);
+                NanoBV::<$type>::new(kani::any(), length)
+            }
+
+            /// Check that the data is masked to the length, and that the length is
+            /// `length`.
+            fn proof_is_masked(&self, length: usize) -> bool {
+                self.len() == length && self.data & !Self::upper_bound(self.length) == 0
+            }
+        }
+    };
+}
+
+ImplNanoBVProofs!(for u8, u16, u32, u64);
+
+macro_rules! ImplNanoBVProofHarness {
+    (for $($type:ident => $module:ident),+) => {
+        $(ImplNanoBVProofHarness!($type, $module);)*
+    };
+
+    ($type:ident, $module:ident) => {
+        mod $module {
+            use crate::NanoBV;
+
+            #[kani::proof]
+            fn new_masked() {
+                let bv = NanoBV::<$type>::proof_any();
+                assert!(bv.proof_is_masked(bv.len()));
+                assert!(bv.set_value(kani::any()).proof_is_masked(bv.len()));
+                assert!(bv.set().proof_is_masked(bv.len()));
+            }
+
+            #[kani::proof]
+            fn bit_ops_masked() {
+                let bv = NanoBV::<$type>::proof_any();
+                let offset: $type = kani::any();
+                kani::assume((offset as usize) < bv.len());
+                assert!(bv.set_bit(offset).proof_is_masked(bv.len()));
+                assert!(bv.clear_bit(offset).proof_is_masked(bv.len()));
+                assert_eq!(bv.set_bit(offset).get_bit(offset), 1);
+                assert_eq!(bv.clear_bit(offset).get_bit(offset), 0);
+            }
+
+            #[kani::proof]
+            fn operators_in_range() {
+                let (a, b) = (NanoBV::<$type>::proof_any(), NanoBV::<$type>::proof_any());
+                let length = crate::internals::min(a.len(), b.len());
+                if a.value().checked_add(b.value()).is_some() {
+                    assert!((a + b).proof_is_masked(length));
+                }
+                if a.value() >= b.value() {
+                    assert!((a - b).proof_is_masked(length));
+                }
+                if a.value().checked_mul(b.value()).is_some() {
+                    assert!((a * b).proof_is_masked(length));
+                }
+                assert!((a & b).proof_is_masked(length));
+                assert!((a | b).proof_is_masked(length));
+                assert!((a ^ b).proof_is_masked(length));
+                if let Some(sum) = a.checked_add(b) {
+                    assert!(sum.proof_is_masked(length));
+                }
+                assert!(a.wrapping_sub(b).proof_is_masked(length));
+                assert!(a.saturating_add(b).proof_is_masked(length));
+            }
+
+            #[kani::proof]
+            fn reverse_involution() {
+                let bv = NanoBV::<$type>::proof_any();
+                assert!(bv.reverse().proof_is_masked(bv.len()));
+                assert_eq!(bv.reverse().reverse(), bv);
+            }
+        }
+    };
+}
+
+ImplNanoBVProofHarness!(for u8 => proof_u8, u16 => proof_u16, u32 => proof_u32, u64 => proof_u64);
@@ -0,0 +1,185 @@
+//! Randomized operations backed by [`picorand`], and an LFSR generator
+//! usable wherever picorand expects one:
+//!
+//! ```
+//! use nanobv::{random::Lfsr, NanoBV};
+//! use picorand::{PicoRandGenerate, RNG};
+//!
+//! let mut rng = RNG::<Lfsr<u16>, u16>::new(0xACE1);
+//! let offset = rng.generate_range(0, 16);
+//! assert!(offset < 16);
+//! assert!(NanoBV::<u16>::ones(16).get_bit(offset) == 1);
+//! let _: u16 = rng.generate();
+//! ```
+
+use crate::NanoBV;
+use picorand::PicoRandRNG;
+
+/// Maximal-length Galois LFSR spanning the full width of its backing type.
+///
+/// Each generated number is the register state after one step per bit, so
+/// consecutive outputs share no bits. The period is `2^n - 1` for an `n`-bit
+/// backing type, and the all-zero state is never reached. This generator is
+/// NOT cryptographically secure.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct Lfsr<T = u64> {
+    state: NanoBV<T>,
+}
+
+macro_rules! ImplNanoBVRandom {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVRandom!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Randomly permute the bits within the declared length, preserving
+            /// the number of set bits.
+            pub fn shuffle<R: PicoRandRNG>(&self, rng: &mut R) -> Self
+            where
+                R::Output: Into<u128>,
+            {
+                let mut shuffled = *self;
+                for i in (1..self.len()).rev() {
+                    let j = rng.rand_range(0, i + 1).into() as usize;
+                    let (bit_i, bit_j) = (shuffled.get_bit(i as $type), shuffled.get_bit(j as $type));
+                    shuffled = shuffled.assign_bit(bit_j, i as $type).assign_bit(bit_i, j as $type);
+                }
+                shuffled
+            }
+        }
+    };
+}
+
+ImplNanoBVRandom!(for u8, u16, u32, u64);
+
+macro_rules! ImplLfsr {
+    (for $(($type:ident, $taps:expr)),+) => {
+        $(impl Lfsr<$type> {
+            /// Galois feedback taps of a maximal-length polynomial.
+            const TAPS: $type = $taps;
+
+            /// Retrieve the current register state.
+            pub const fn state(&self) -> NanoBV<$type> {
+                self.state
+            }
+
+            /// Advance the register by one step, returning the bit shifted out.
+            pub const fn step(&mut self) -> bool {
+                let (data, out) = (self.state.value(), self.state.value() & 1 == 1);
+                self.state = self.state.set_value((data >> 1) ^ (Self::TAPS * out as $type));
+                out
+            }
+        }
+
+        impl PicoRandRNG for Lfsr<$type> {
+            type Input = $type;
+            type Output = $type;
+
+            /// Create an [`Lfsr`] seeded with `seed`. A zero seed, which would lock
+            /// the register, is replaced by 1.
+            fn new(seed: $type) -> Self {
+                Lfsr { state: NanoBV::<$type>::new(seed.max(1), NanoBV::<$type>::BIT_SIZE) }
+            }
+
+            fn rand(&mut self) -> $type {
+                for _ in 0..NanoBV::<$type>::BIT_SIZE {
+                    self.step();
+                }
+                self.state.value()
+            }
+
+            /// Generate a number in `min..max`, or `min` if the range is empty,
+            /// scaling the random value into the range by multiply-shift. The
+            /// range must fit in the backing type.
+            fn rand_range(&mut self, min: usize, max: usize) -> $type {
+                match max.saturating_sub(min) as u128 {
+                0 => min as $type,
+                span => (min as u128 + ((self.rand() as u64 as u128 * span) >> NanoBV::<$type>::BIT_SIZE)) as $type,
+                }
+            }
+        })*
+    };
+}
+
+ImplLfsr!(for (u8, 0xB8), (u16, 0xB400), (u32, 0x8020_0003), (u64, 0xD800_0000_0000_0000));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVRandomTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVRandomTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_shuffle_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let mut wyrand = WyRand::new(0xDEAD_BEEF);
+                let shuffled = bv.shuffle(&mut wyrand);
+                assert_eq!((shuffled.len(), shuffled.count_ones()), (bv.len(), bv.count_ones()));
+                assert_eq!(NBV::new(0b1, 1).shuffle(&mut wyrand), NBV::new(0b1, 1));
+                let bv = NBV::new(0b0000_0001, 8);
+                let mut seen = 0;
+                for _ in 0..256 {
+                    seen |= bv.shuffle(&mut wyrand).value();
+                }
+                assert_eq!(seen, 0xFF);
+            }
+        }
+        };
+    }
+
+    ImplNanoBVRandomTest!(for u8, u16, u32, u64);
+
+    #[test]
+    fn test_lfsr_period() {
+        let mut lfsr = Lfsr::<u8>::new(0);
+        let mut seen = [false; 256];
+        for _ in 0..255 {
+            let value = lfsr.rand();
+            assert!(!seen[value as usize]);
+            seen[value as usize] = true;
+        }
+        assert!(!seen[0]);
+        assert_eq!(lfsr, Lfsr::<u8>::new(1));
+        let mut lfsr = Lfsr::<u16>::new(1);
+        let mut steps = 1;
+        lfsr.step();
+        while lfsr.state() != NanoBV::<u16>::new(1, 16) {
+            lfsr.step();
+            steps += 1;
+        }
+        assert_eq!(steps, 65535);
+    }
+
+    #[test]
+    fn test_lfsr_picorand() {
+        let mut rng = RNG::<Lfsr<u32>, u8>::new(0xDEAD_BEEF);
+        for _ in 0..64 {
+            let value = rng.generate_range(10, 20);
+            assert!((10..20).contains(&value));
+        }
+        assert_eq!(Lfsr::<u64>::new(5).rand_range(3, 3), 3);
+        let mut lfsr = Lfsr::<u8>::new(0x5A);
+        for span in [1, 3, 7, 100, 255] {
+            for _ in 0..64 {
+                assert!(lfsr.rand_range(1, 1 + span) - 1 < span as u8);
+            }
+        }
+        let mut lfsr = Lfsr::<u64>::new(0x1234);
+        for _ in 0..64 {
+            assert!(lfsr.rand_range(0, 1_000_003) < 1_000_003);
+        }
+        let bv = NanoBV::<u64>::new(0xF0F0, 64);
+        let shuffled = bv.shuffle(&mut Lfsr::<u64>::new(0x1234));
+        assert_eq!(shuffled.count_ones(), bv.count_ones());
+    }
+}
@@ -0,0 +1,278 @@
+//! Regrouping of bits into narrow symbols, as in Base64 and Base32.
+//!
+//! Bits are taken most significant bit first, so the first symbol holds the
+//! leading bits of the first byte, as RFC 4648 specifies. A last partial
+//! symbol is padded with zero bits on the right. Mapping the symbols to an
+//! alphabet and appending `=` padding characters is left to the caller:
+//!
+//! ```
+//! use nanobv::regroup::{padding_len, regroup_bytes, ungroup_symbols};
+//!
+//! const ALPHABET: &[u8; 64] =
+//!     b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+//!
+//! let mut symbols = [0; 8];
+//! let count = regroup_bytes(b"Ma", 6, &mut symbols).unwrap();
+//! assert_eq!(count, 3);
+//! assert_eq!(padding_len(count, 6), 1);
+//! let encoded: Vec<u8> = symbols[..count].iter().map(|&s| ALPHABET[s as usize]).collect();
+//! assert_eq!(encoded, b"TWE");
+//!
+//! let mut bytes = [0; 2];
+//! assert_eq!(ungroup_symbols(&symbols[..count], 6, &mut bytes), Ok(2));
+//! assert_eq!(&bytes, b"Ma");
+//! ```
+
+use crate::{Error, NanoBV};
+
+/// Retrieve the number of symbols of `width` bits holding `byte_len` bytes.
+pub const fn regrouped_len(byte_len: usize, width: usize) -> usize {
+    assert!(width >= 1 && width <= 8, "Invalid width provided.");
+    symbol_len(byte_len * 8, width)
+}
+
+/// Retrieve the number of padding characters completing `symbol_count`
+/// symbols of `width` bits to a whole number of bytes and symbols: a multiple
+/// of 4 symbols for Base64 and of 8 symbols for Base32.
+pub const fn padding_len(symbol_count: usize, width: usize) -> usize {
+    assert!(width >= 1 && width <= 8, "Invalid width provided.");
+    // Symbols per group, always a power of two.
+    let group = [1, 1, 1, 8, 2, 8, 4, 8, 1][width];
+    (group - (symbol_count & (group - 1))) & (group - 1)
+}
+
+/// Split `bytes` into symbols of `width` bits, returning the number of
+/// symbols written to `out`.
+pub fn regroup_bytes(
+    bytes: &[u8],
+    width: usize,
+    out: &mut [u8],
+) -> Result<usize, Error> {
+    let count = regrouped_len(bytes.len(), width);
+    let out = out.get_mut(..count).ok_or(Error::BufferTooSmall)?;
+    let (mut pending, mut bits, mut symbols) = (0u16, 0, out.iter_mut());
+    for &byte in bytes {
+        pending = (pending << 8) | byte as u16;
+        bits += 8;
+        while bits >= width {
+            bits -= width;
+            *symbols.next().ok_or(Error::BufferTooSmall)? =
+                (pending >> bits) as u8 & mask(width);
+        }
+    }
+    if bits != 0 {
+        *symbols.next().ok_or(Error::BufferTooSmall)? =
+            (pending << (width - bits)) as u8 & mask(width);
+    }
+    Ok(count)
+}
+
+/// Join symbols of `width` bits back into bytes, returning the number of
+/// bytes written to `out`.
+///
+/// Returns [`Error::InvalidDigit`] for a symbol wider than `width` bits, and
+/// [`Error::InvalidFormat`] if the bits left after the last whole byte make
+/// up a whole symbol or are not zero, as no [`regroup_bytes`] output has
+/// them.
+pub fn ungroup_symbols(
+    symbols: &[u8],
+    width: usize,
+    out: &mut [u8],
+) -> Result<usize, Error> {
+    assert!((1..=8).contains(&width), "Invalid width provided.");
+    let count = symbols.len() * width / 8;
+    let out = out.get_mut(..count).ok_or(Error::BufferTooSmall)?;
+    let (mut pending, mut bits, mut bytes) = (0u16, 0, out.iter_mut());
+    for &symbol in symbols {
+        if symbol & !mask(width) != 0 {
+            return Err(Error::InvalidDigit);
+        }
+        pending = (pending << width) | symbol as u16;
+        bits += width;
+        if bits >= 8 {
+            bits -= 8;
+            *bytes.next().ok_or(Error::BufferTooSmall)? =
+                (pending >> bits) as u8;
+        }
+    }
+    match bits < width && pending & ((1 << bits) - 1) == 0 {
+        true => Ok(count),
+        false => Err(Error::InvalidFormat),
+    }
+}
+
+/// Retrieve the number of symbols of `width` bits covering `bits` bits,
+/// dividing by a constant for each width.
+const fn symbol_len(bits: usize, width: usize) -> usize {
+    match width {
+        1 => bits,
+        2 => bits.div_ceil(2),
+        3 => bits.div_ceil(3),
+        4 => bits.div_ceil(4),
+        5 => bits.div_ceil(5),
+        6 => bits.div_ceil(6),
+        7 => bits.div_ceil(7),
+        _ => bits.div_ceil(8),
+    }
+}
+
+/// Mask of the low `width` bits of a byte.
+const fn mask(width: usize) -> u8 {
+    u8::MAX >> (8 - width)
+}
+
+macro_rules! ImplNanoBVRegroup {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVRegroup!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Split the current NanoBV into symbols of `width` bits, most
+            /// significant bits first, returning the number of symbols written to
+            /// `out`. A last partial symbol is padded with zero bits on the right.
+            pub fn regroup(&self, width: usize, out: &mut [u8]) -> Result<usize, Error> {
+                ["Invalid width provided."][(width == 0 || width > 8) as usize];
+                let count = symbol_len(self.len(), width);
+                let out = out.get_mut(..count).ok_or(Error::BufferTooSmall)?;
+                let padded = (self.data as u128) << (count * width - self.len());
+                for (i, symbol) in out.iter_mut().enumerate() {
+                    *symbol = (padded >> ((count - 1 - i) * width)) as u8 & mask(width);
+                }
+                Ok(count)
+            }
+
+            /// Join symbols of `width` bits, most significant bits first, into a
+            /// NanoBV of `length` bits.
+            ///
+            /// Returns [`Error::InvalidLength`] if `length` is invalid or the
+            /// symbol count differs from that of [`regroup`](Self::regroup),
+            /// [`Error::InvalidDigit`] for a symbol wider than `width` bits, and
+            /// [`Error::InvalidFormat`] if the padding bits are not zero.
+            pub fn ungroup(symbols: &[u8], width: usize, length: usize) -> Result<Self, Error> {
+                ["Invalid width provided."][(width == 0 || width > 8) as usize];
+                NanoBV::<$type>::try_new(0, length)?;
+                if symbols.len() != symbol_len(length, width) {
+                    return Err(Error::InvalidLength);
+                }
+                let mut padded = 0u128;
+                for &symbol in symbols {
+                    if symbol & !mask(width) != 0 {
+                        return Err(Error::InvalidDigit);
+                    }
+                    padded = (padded << width) | symbol as u128;
+                }
+                let padding = symbols.len() * width - length;
+                match padded & ((1 << padding) - 1) {
+                0 => Ok(NanoBV::<$type>::new((padded >> padding) as $type, length)),
+                _ => Err(Error::InvalidFormat),
+                }
+            }
+        }
+    };
+}
+
+ImplNanoBVRegroup!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    #[test]
+    fn test_regroup_bytes() {
+        let mut symbols = [0; 16];
+        // RFC 4648, section 10: "foob" is "Zm9vYg==" and "MZXW6YQ=" in Base32.
+        assert_eq!(regroup_bytes(b"foob", 6, &mut symbols), Ok(6));
+        assert_eq!(symbols[..6], [25, 38, 61, 47, 24, 32]);
+        assert_eq!(padding_len(6, 6), 2);
+        assert_eq!(regroup_bytes(b"foob", 5, &mut symbols), Ok(7));
+        assert_eq!(symbols[..7], [12, 25, 23, 22, 30, 24, 16]);
+        assert_eq!(padding_len(7, 5), 1);
+        assert_eq!(padding_len(8, 5), 0);
+        assert_eq!(regroup_bytes(b"", 6, &mut symbols), Ok(0));
+        assert_eq!(
+            regroup_bytes(b"foob", 6, &mut symbols[..5]),
+            Err(Error::BufferTooSmall)
+        );
+        let mut bytes = [0; 4];
+        assert_eq!(
+            ungroup_symbols(&[25, 38, 61, 47, 24, 32], 6, &mut bytes),
+            Ok(4)
+        );
+        assert_eq!(&bytes, b"foob");
+        assert_eq!(
+            ungroup_symbols(&[12, 25, 23, 22, 30, 24, 16], 5, &mut bytes),
+            Ok(4)
+        );
+        assert_eq!(&bytes, b"foob");
+    }
+
+    #[test]
+    fn test_regrouped_len() {
+        for width in 1..=8 {
+            for byte_len in 0..=40 {
+                let count = regrouped_len(byte_len, width);
+                assert_eq!(count, (byte_len * 8).div_ceil(width));
+                let padded = count + padding_len(count, width);
+                assert_eq!((padded * width) % 8, 0);
+                assert!(padding_len(count, width) < 8);
+            }
+        }
+        assert_eq!(
+            regrouped_len(usize::MAX / 8, 3),
+            (usize::MAX / 8 * 8).div_ceil(3)
+        );
+    }
+
+    #[test]
+    fn test_ungroup_symbols_invalid() {
+        let mut bytes = [0; 4];
+        assert_eq!(
+            ungroup_symbols(&[25, 38, 61, 47, 24, 33], 6, &mut bytes),
+            Err(Error::InvalidFormat)
+        );
+        assert_eq!(
+            ungroup_symbols(&[25, 38, 61, 47, 24], 6, &mut bytes),
+            Err(Error::InvalidFormat)
+        );
+        assert_eq!(
+            ungroup_symbols(&[25, 64], 6, &mut bytes),
+            Err(Error::InvalidDigit)
+        );
+        assert_eq!(
+            ungroup_symbols(&[25, 38, 61, 47, 24, 32], 6, &mut bytes[..3]),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    macro_rules! ImplNanoBVRegroupTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVRegroupTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_regroup_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut symbols = [0; 64];
+                let bv = NBV::new(0b1011_0011, 8);
+                assert_eq!(bv.regroup(6, &mut symbols), Ok(2));
+                assert_eq!(symbols[..2], [0b101100, 0b110000]);
+                assert_eq!(NBV::ungroup(&symbols[..2], 6, 8), Ok(bv));
+                assert_eq!(bv.regroup(8, &mut symbols[..0]), Err(Error::BufferTooSmall));
+                assert_eq!(NBV::ungroup(&[0b101100, 0b110001], 6, 8), Err(Error::InvalidFormat));
+                assert_eq!(NBV::ungroup(&[0b101100], 6, 8), Err(Error::InvalidLength));
+                assert_eq!(NBV::ungroup(&[0b1000000], 6, 6), Err(Error::InvalidDigit));
+                let ones = NBV::ones(NBV::BIT_SIZE);
+                let count = ones.regroup(5, &mut symbols).unwrap();
+                assert_eq!(count, NBV::BIT_SIZE.div_ceil(5));
+                assert_eq!(NBV::ungroup(&symbols[..count], 5, NBV::BIT_SIZE), Ok(ones));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVRegroupTest!(for u8, u16, u32, u64);
+}
@@ -0,0 +1,389 @@
+//! RISC-V Zbb and Zbs bit-manipulation instructions.
+//!
+//! [`rv32`] and [`rv64`] implement each instruction as a function of its
+//! source registers, following the ratified specification (version 1.0.0)
+//! including its edge cases: `clz` and `ctz` of zero return XLEN, shift and
+//! bit-index amounts only use their low log2(XLEN) bits, and the RV64 word
+//! instructions sign-extend their 32-bit result. Register operands shorter
+//! than XLEN are zero extended, and results are XLEN bits long. Immediate
+//! forms panic on an immediate that has no valid encoding.
+//!
+//! ```
+//! use nanobv::{riscv::rv64, NanoBV};
+//!
+//! let rs1 = NanoBV::<u64>::new(0x0000_0000_8000_0001, 64);
+//! assert_eq!(rv64::clz(rs1).value(), 32);
+//! assert_eq!(rv64::rorw(rs1, NanoBV::<u64>::new(1, 64)).value(), 0xFFFF_FFFF_C000_0000);
+//! assert_eq!(rv64::orc_b(rs1).value(), 0x0000_0000_FF00_00FF);
+//! ```
+//!
+//! The bit-field deposit and extract instructions of the draft Zbe extension
+//! were not ratified; [`NanoBV::scatter`](crate::NanoBV::scatter) and
+//! [`NanoBV::gather`](crate::NanoBV::gather) cover them.
+
+macro_rules! ImplRiscV {
+    ($type:ident, $signed:ident) => {
+        use crate::NanoBV;
+
+        /// Register width in bits.
+        pub const XLEN: usize = $type::BITS as usize;
+
+        const fn reg(value: $type) -> NanoBV<$type> {
+            NanoBV::<$type>::new(value, XLEN)
+        }
+
+        const fn shamt(rs2: NanoBV<$type>) -> u32 {
+            (rs2.value() & (XLEN as $type - 1)) as u32
+        }
+
+        const fn imm(shamt: usize) -> u32 {
+            ["Invalid offset provided."][(shamt >= XLEN) as usize];
+            shamt as u32
+        }
+
+        /// AND with inverted operand.
+        pub const fn andn(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(rs1.value() & !rs2.value())
+        }
+
+        /// OR with inverted operand.
+        pub const fn orn(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(rs1.value() | !rs2.value())
+        }
+
+        /// Exclusive NOR.
+        pub const fn xnor(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(!(rs1.value() ^ rs2.value()))
+        }
+
+        /// Count leading zero bits, XLEN for zero.
+        pub const fn clz(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            reg(rs1.value().leading_zeros() as $type)
+        }
+
+        /// Count trailing zero bits, XLEN for zero.
+        pub const fn ctz(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            reg(rs1.value().trailing_zeros() as $type)
+        }
+
+        /// Count set bits.
+        pub const fn cpop(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            reg(rs1.value().count_ones() as $type)
+        }
+
+        /// Signed maximum.
+        pub const fn max(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            match (rs1.value() as $signed) < (rs2.value() as $signed) {
+                true => reg(rs2.value()),
+                false => reg(rs1.value()),
+            }
+        }
+
+        /// Unsigned maximum.
+        pub const fn maxu(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            match rs1.value() < rs2.value() {
+                true => reg(rs2.value()),
+                false => reg(rs1.value()),
+            }
+        }
+
+        /// Signed minimum.
+        pub const fn min(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            match (rs1.value() as $signed) < (rs2.value() as $signed) {
+                true => reg(rs1.value()),
+                false => reg(rs2.value()),
+            }
+        }
+
+        /// Unsigned minimum.
+        pub const fn minu(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            match rs1.value() < rs2.value() {
+                true => reg(rs1.value()),
+                false => reg(rs2.value()),
+            }
+        }
+
+        /// Sign-extend the low byte.
+        pub const fn sext_b(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            reg(rs1.value() as i8 as $signed as $type)
+        }
+
+        /// Sign-extend the low halfword.
+        pub const fn sext_h(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            reg(rs1.value() as i16 as $signed as $type)
+        }
+
+        /// Zero-extend the low halfword.
+        pub const fn zext_h(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            reg(rs1.value() as u16 as $type)
+        }
+
+        /// Rotate left by the low log2(XLEN) bits of `rs2`.
+        pub const fn rol(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(rs1.value().rotate_left(shamt(rs2)))
+        }
+
+        /// Rotate right by the low log2(XLEN) bits of `rs2`.
+        pub const fn ror(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(rs1.value().rotate_right(shamt(rs2)))
+        }
+
+        /// Rotate right by an immediate below XLEN.
+        pub const fn rori(rs1: NanoBV<$type>, shamt: usize) -> NanoBV<$type> {
+            reg(rs1.value().rotate_right(imm(shamt)))
+        }
+
+        /// Set each byte to all ones if any of its bits is set, and to zero
+        /// otherwise.
+        pub const fn orc_b(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            let (mut combined, mut i) = (0 as $type, 0);
+            while i < XLEN {
+                if (rs1.value() >> i) as u8 != 0 {
+                    combined |= 0xFF << i;
+                }
+                i += 8;
+            }
+            reg(combined)
+        }
+
+        /// Reverse the byte order.
+        pub const fn rev8(rs1: NanoBV<$type>) -> NanoBV<$type> {
+            reg(rs1.value().swap_bytes())
+        }
+
+        /// Clear the bit indexed by the low log2(XLEN) bits of `rs2`.
+        pub const fn bclr(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(rs1.value() & !(1 << shamt(rs2)))
+        }
+
+        /// Clear the bit at an immediate index below XLEN.
+        pub const fn bclri(rs1: NanoBV<$type>, shamt: usize) -> NanoBV<$type> {
+            reg(rs1.value() & !(1 << imm(shamt)))
+        }
+
+        /// Extract the bit indexed by the low log2(XLEN) bits of `rs2`.
+        pub const fn bext(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg((rs1.value() >> shamt(rs2)) & 1)
+        }
+
+        /// Extract the bit at an immediate index below XLEN.
+        pub const fn bexti(rs1: NanoBV<$type>, shamt: usize) -> NanoBV<$type> {
+            reg((rs1.value() >> imm(shamt)) & 1)
+        }
+
+        /// Invert the bit indexed by the low log2(XLEN) bits of `rs2`.
+        pub const fn binv(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(rs1.value() ^ (1 << shamt(rs2)))
+        }
+
+        /// Invert the bit at an immediate index below XLEN.
+        pub const fn binvi(rs1: NanoBV<$type>, shamt: usize) -> NanoBV<$type> {
+            reg(rs1.value() ^ (1 << imm(shamt)))
+        }
+
+        /// Set the bit indexed by the low log2(XLEN) bits of `rs2`.
+        pub const fn bset(
+            rs1: NanoBV<$type>,
+            rs2: NanoBV<$type>,
+        ) -> NanoBV<$type> {
+            reg(rs1.value() | (1 << shamt(rs2)))
+        }
+
+        /// Set the bit at an immediate index below XLEN.
+        pub const fn bseti(rs1: NanoBV<$type>, shamt: usize) -> NanoBV<$type> {
+            reg(rs1.value() | (1 << imm(shamt)))
+        }
+    };
+}
+
+/// RV32 instructions.
+pub mod rv32 {
+    ImplRiscV!(u32, i32);
+}
+
+/// RV64 instructions, including the word forms operating on the low 32 bits.
+pub mod rv64 {
+    ImplRiscV!(u64, i64);
+
+    const fn word(value: u32) -> NanoBV<u64> {
+        reg(value as i32 as u64)
+    }
+
+    /// Count leading zero bits of the low word, 32 for zero.
+    pub const fn clzw(rs1: NanoBV<u64>) -> NanoBV<u64> {
+        reg((rs1.value() as u32).leading_zeros() as u64)
+    }
+
+    /// Count trailing zero bits of the low word, 32 for zero.
+    pub const fn ctzw(rs1: NanoBV<u64>) -> NanoBV<u64> {
+        reg((rs1.value() as u32).trailing_zeros() as u64)
+    }
+
+    /// Count set bits of the low word.
+    pub const fn cpopw(rs1: NanoBV<u64>) -> NanoBV<u64> {
+        reg((rs1.value() as u32).count_ones() as u64)
+    }
+
+    /// Rotate the low word left by the low 5 bits of `rs2`, sign-extending
+    /// the result.
+    pub const fn rolw(rs1: NanoBV<u64>, rs2: NanoBV<u64>) -> NanoBV<u64> {
+        word((rs1.value() as u32).rotate_left(rs2.value() as u32 & 31))
+    }
+
+    /// Rotate the low word right by the low 5 bits of `rs2`, sign-extending
+    /// the result.
+    pub const fn rorw(rs1: NanoBV<u64>, rs2: NanoBV<u64>) -> NanoBV<u64> {
+        word((rs1.value() as u32).rotate_right(rs2.value() as u32 & 31))
+    }
+
+    /// Rotate the low word right by an immediate below 32, sign-extending
+    /// the result.
+    pub const fn roriw(rs1: NanoBV<u64>, shamt: usize) -> NanoBV<u64> {
+        assert!(shamt < 32, "Invalid offset provided.");
+        word((rs1.value() as u32).rotate_right(shamt as u32))
+    }
+
+    /// Zero-extend the low word, as `add.uw` with a zero `rs2` (`zext.w`).
+    pub const fn zext_w(rs1: NanoBV<u64>) -> NanoBV<u64> {
+        reg(rs1.value() as u32 as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NanoBV;
+
+    const fn x32(value: u32) -> NanoBV<u32> {
+        NanoBV::<u32>::new(value, 32)
+    }
+
+    const fn x64(value: u64) -> NanoBV<u64> {
+        NanoBV::<u64>::new(value, 64)
+    }
+
+    #[test]
+    fn test_riscv_logic() {
+        assert_eq!(rv32::andn(x32(0b1100), x32(0b1010)), x32(0b0100));
+        assert_eq!(rv32::orn(x32(0b1100), x32(0xFFFF_FFF5)), x32(0b1110));
+        assert_eq!(rv64::xnor(x64(0), x64(u64::MAX)), x64(0));
+        assert_eq!(rv64::andn(NanoBV::<u64>::new(0xF, 4), x64(1)), x64(0xE));
+    }
+
+    #[test]
+    fn test_riscv_count() {
+        assert_eq!((rv32::clz(x32(0)), rv32::ctz(x32(0))), (x32(32), x32(32)));
+        assert_eq!((rv64::clz(x64(0)), rv64::ctz(x64(0))), (x64(64), x64(64)));
+        assert_eq!(rv32::clz(x32(1)), x32(31));
+        assert_eq!(rv64::ctz(x64(0x8000_0000_0000_0000)), x64(63));
+        assert_eq!(rv32::cpop(x32(u32::MAX)), x32(32));
+        assert_eq!(rv64::clzw(x64(0xFFFF_FFFF_0000_0000)), x64(32));
+        assert_eq!(rv64::ctzw(x64(0x1_0000_0000)), x64(32));
+        assert_eq!(rv64::cpopw(x64(u64::MAX)), x64(32));
+    }
+
+    #[test]
+    fn test_riscv_min_max() {
+        let (negative, positive) = (x32(0xFFFF_FFFF), x32(1));
+        assert_eq!(rv32::min(negative, positive), negative);
+        assert_eq!(rv32::max(negative, positive), positive);
+        assert_eq!(rv32::minu(negative, positive), positive);
+        assert_eq!(rv32::maxu(negative, positive), negative);
+        assert_eq!(
+            rv64::min(x64(i64::MIN as u64), x64(0)),
+            x64(i64::MIN as u64)
+        );
+        assert_eq!(rv64::maxu(x64(5), x64(5)), x64(5));
+    }
+
+    #[test]
+    fn test_riscv_extend() {
+        assert_eq!(rv32::sext_b(x32(0x1234_5680)), x32(0xFFFF_FF80));
+        assert_eq!(rv32::sext_b(x32(0x1234_567F)), x32(0x7F));
+        assert_eq!(rv64::sext_h(x64(0x8000)), x64(0xFFFF_FFFF_FFFF_8000));
+        assert_eq!(rv64::zext_h(x64(u64::MAX)), x64(0xFFFF));
+        assert_eq!(rv64::zext_w(x64(u64::MAX)), x64(0xFFFF_FFFF));
+    }
+
+    #[test]
+    fn test_riscv_rotate() {
+        assert_eq!(rv32::rol(x32(0x8000_0001), x32(1)), x32(0x0000_0003));
+        assert_eq!(rv32::ror(x32(0x8000_0001), x32(33)), x32(0xC000_0000));
+        assert_eq!(rv64::ror(x64(1), x64(64)), x64(1));
+        assert_eq!(rv32::rori(x32(1), 0), x32(1));
+        assert_eq!(
+            rv64::rolw(x64(0x4000_0000), x64(1)),
+            x64(0xFFFF_FFFF_8000_0000)
+        );
+        assert_eq!(rv64::rorw(x64(0xFFFF_FFFF_0000_0002), x64(33)), x64(1));
+        assert_eq!(rv64::roriw(x64(1), 1), x64(0xFFFF_FFFF_8000_0000));
+    }
+
+    #[test]
+    fn test_riscv_bytes() {
+        assert_eq!(rv32::orc_b(x32(0x0001_8000)), x32(0x00FF_FF00));
+        assert_eq!(
+            rv64::orc_b(x64(0x0100_0000_0000_0000)),
+            x64(0xFF00_0000_0000_0000)
+        );
+        assert_eq!(rv32::rev8(x32(0x1234_5678)), x32(0x7856_3412));
+        assert_eq!(
+            rv64::rev8(x64(0x0102_0304_0506_0708)),
+            x64(0x0807_0605_0403_0201)
+        );
+    }
+
+    #[test]
+    fn test_riscv_single_bit() {
+        assert_eq!(rv32::bset(x32(0), x32(35)), x32(0b1000));
+        assert_eq!(rv32::bclr(x32(u32::MAX), x32(31)), x32(0x7FFF_FFFF));
+        assert_eq!(rv64::binv(x64(0), x64(63)), x64(0x8000_0000_0000_0000));
+        assert_eq!(rv64::bext(x64(0x8000_0000_0000_0000), x64(127)), x64(1));
+        assert_eq!(rv32::bseti(x32(0), 31), x32(0x8000_0000));
+        assert_eq!(rv32::bclri(x32(0b11), 0), x32(0b10));
+        assert_eq!(rv64::binvi(x64(1), 0), x64(0));
+        assert_eq!(rv64::bexti(x64(0b100), 2), x64(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_riscv_invalid_immediate() {
+        rv32::bseti(x32(0), 32);
+    }
+}
@@ -0,0 +1,229 @@
+//! Additive and self-synchronizing scramblers.
+//!
+//! Polynomials are described as a [`NanoBV`] whose length is the degree of
+//! the polynomial and whose bit `k - 1` holds the coefficient of `x^k` (the
+//! constant term is implied).
+
+use crate::NanoBV;
+
+/// x^7 + x^6 + 1 (PRBS7).
+pub const X7_X6_1: NanoBV<u8> = NanoBV::<u8>::new(0b110_0000, 7);
+
+/// x^7 + x^4 + 1 (IEEE 802.11 data scrambler).
+pub const X7_X4_1: NanoBV<u8> = NanoBV::<u8>::new(0b100_1000, 7);
+
+/// x^16 + x^5 + x^4 + x^3 + 1 (PCI Express 8b/10b scrambler).
+pub const X16_X5_X4_X3_1: NanoBV<u16> = NanoBV::<u16>::new(0x801C, 16);
+
+/// x^58 + x^39 + 1 (IEEE 802.3 64b/66b PCS scrambler).
+pub const X58_X39_1: NanoBV<u64> =
+    NanoBV::<u64>::new((1 << 57) | (1 << 38), 58);
+
+/// Additive (synchronous) scrambler driven by a free-running LFSR.
+///
+/// Scrambling and descrambling are the same operation, so a descrambler is
+/// simply an [`AdditiveScrambler`] seeded identically to the transmitter.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct AdditiveScrambler<T = u32> {
+    polynomial: NanoBV<T>,
+    state: NanoBV<T>,
+}
+
+/// Self-synchronizing (multiplicative) scrambler.
+///
+/// The descrambler recovers from an arbitrary initial state after
+/// `polynomial.len()` bits have been received.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct SelfSyncScrambler<T = u32> {
+    polynomial: NanoBV<T>,
+    state: NanoBV<T>,
+}
+
+macro_rules! ImplScrambler {
+    (for $($type:tt),+) => {
+        $(ImplScrambler!($type);)*
+    };
+
+    ($type:ident) => {
+        impl AdditiveScrambler<$type> {
+            /// Create an [`AdditiveScrambler`] for `polynomial` seeded with `seed`.
+            pub const fn new(polynomial: NanoBV<$type>, seed: $type) -> Self {
+                AdditiveScrambler { polynomial, state: NanoBV::<$type>::new(seed, polynomial.len()) }
+            }
+
+            /// Retrieve the current LFSR state.
+            pub const fn state(&self) -> NanoBV<$type> {
+                self.state
+            }
+
+            /// Advance the LFSR by one step, returning the keystream bit.
+            pub fn next_bit(&mut self) -> $type {
+                let feedback = (self.state.value() & self.polynomial.value()).count_ones() as $type & 1;
+                self.state = self.state.set_value((self.state.value() << 1) | feedback);
+                feedback
+            }
+
+            /// Scramble the bits of `word`, least significant bit first.
+            pub fn scramble(&mut self, word: NanoBV<$type>) -> NanoBV<$type> {
+                let mut data = word.value();
+                for offset in 0..word.len() {
+                    data ^= self.next_bit() << offset;
+                }
+                word.set_value(data)
+            }
+
+            /// Scramble `data` in place, least significant bit of each byte first.
+            pub fn scramble_bytes(&mut self, data: &mut [u8]) {
+                for byte in data.iter_mut() {
+                    for offset in 0..8 {
+                        *byte ^= (self.next_bit() as u8) << offset;
+                    }
+                }
+            }
+        }
+
+        impl SelfSyncScrambler<$type> {
+            /// Create a [`SelfSyncScrambler`] for `polynomial` seeded with `seed`.
+            pub const fn new(polynomial: NanoBV<$type>, seed: $type) -> Self {
+                SelfSyncScrambler { polynomial, state: NanoBV::<$type>::new(seed, polynomial.len()) }
+            }
+
+            /// Retrieve the current shift register state.
+            pub const fn state(&self) -> NanoBV<$type> {
+                self.state
+            }
+
+            const fn feedback(&self) -> $type {
+                (self.state.value() & self.polynomial.value()).count_ones() as $type & 1
+            }
+
+            /// Scramble a single bit.
+            pub fn scramble_bit(&mut self, bit: $type) -> $type {
+                let scrambled = (bit & 1) ^ self.feedback();
+                self.state = self.state.set_value((self.state.value() << 1) | scrambled);
+                scrambled
+            }
+
+            /// Descramble a single bit.
+            pub fn descramble_bit(&mut self, bit: $type) -> $type {
+                let descrambled = (bit & 1) ^ self.feedback();
+                self.state = self.state.set_value((self.state.value() << 1) | (bit & 1));
+                descrambled
+            }
+
+            /// Scramble the bits of `word`, least significant bit first.
+            pub fn scramble(&mut self, word: NanoBV<$type>) -> NanoBV<$type> {
+                let mut data = 0;
+                for offset in 0..word.len() {
+                    data |= self.scramble_bit(word.value() >> offset) << offset;
+                }
+                word.set_value(data)
+            }
+
+            /// Descramble the bits of `word`, least significant bit first.
+            pub fn descramble(&mut self, word: NanoBV<$type>) -> NanoBV<$type> {
+                let mut data = 0;
+                for offset in 0..word.len() {
+                    data |= self.descramble_bit(word.value() >> offset) << offset;
+                }
+                word.set_value(data)
+            }
+
+            /// Scramble `data` in place, least significant bit of each byte first.
+            pub fn scramble_bytes(&mut self, data: &mut [u8]) {
+                for byte in data.iter_mut() {
+                    let mut scrambled = 0;
+                    for offset in 0..8 {
+                        scrambled |= (self.scramble_bit((*byte >> offset) as $type & 1) as u8) << offset;
+                    }
+                    *byte = scrambled;
+                }
+            }
+
+            /// Descramble `data` in place, least significant bit of each byte first.
+            pub fn descramble_bytes(&mut self, data: &mut [u8]) {
+                for byte in data.iter_mut() {
+                    let mut descrambled = 0;
+                    for offset in 0..8 {
+                        descrambled |= (self.descramble_bit((*byte >> offset) as $type & 1) as u8) << offset;
+                    }
+                    *byte = descrambled;
+                }
+            }
+        }
+    };
+}
+
+ImplScrambler!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_additive_prbs7_period() {
+        let mut scrambler = AdditiveScrambler::<u8>::new(X7_X6_1, 0x7F);
+        let seed = scrambler.state();
+        for _ in 1..127 {
+            scrambler.next_bit();
+            assert_ne!(scrambler.state(), seed);
+        }
+        scrambler.next_bit();
+        assert_eq!(scrambler.state(), seed);
+    }
+
+    #[test]
+    fn test_additive_ieee80211_sequence() {
+        // First 16 bits of the sequence given in IEEE 802.11 for an all-ones
+        // initial state.
+        let expected = [0, 0, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 0, 0, 1, 0];
+        let mut scrambler = AdditiveScrambler::<u8>::new(X7_X4_1, 0x7F);
+        for &bit in expected.iter() {
+            assert_eq!(scrambler.next_bit(), bit);
+        }
+    }
+
+    #[test]
+    fn test_additive_roundtrip() {
+        let original = *b"nanobv scrambler";
+        let mut data = original;
+        AdditiveScrambler::<u16>::new(X16_X5_X4_X3_1, 0xFFFF)
+            .scramble_bytes(&mut data);
+        assert_ne!(data, original);
+        AdditiveScrambler::<u16>::new(X16_X5_X4_X3_1, 0xFFFF)
+            .scramble_bytes(&mut data);
+        assert_eq!(data, original);
+
+        let word = NanoBV::<u32>::new(0xDEAD_BEEF, 32);
+        let mut tx = AdditiveScrambler::<u32>::new(
+            NanoBV::<u32>::new(X7_X4_1.value() as u32, 7),
+            0x55,
+        );
+        let mut rx = tx;
+        assert_eq!(rx.scramble(tx.scramble(word)), word);
+    }
+
+    #[test]
+    fn test_self_sync_roundtrip() {
+        let original = *b"self-synchronizing scrambler";
+        let mut data = original;
+        SelfSyncScrambler::<u64>::new(X58_X39_1, 0x1234_5678)
+            .scramble_bytes(&mut data);
+        assert_ne!(data, original);
+        SelfSyncScrambler::<u64>::new(X58_X39_1, 0x1234_5678)
+            .descramble_bytes(&mut data);
+        assert_eq!(data, original);
+    }
+
+    #[test]
+    fn test_self_sync_recovers() {
+        let mut tx = SelfSyncScrambler::<u8>::new(X7_X6_1, 0x2A);
+        let mut rx = SelfSyncScrambler::<u8>::new(X7_X6_1, 0x55);
+        let word = NanoBV::<u8>::new(0xA5, 8);
+        // The first word may be corrupted while the descrambler synchronizes.
+        rx.descramble(tx.scramble(word));
+        for _ in 0..8 {
+            assert_eq!(rx.descramble(tx.scramble(word)), word);
+        }
+    }
+}
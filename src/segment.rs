@@ -0,0 +1,179 @@
+//! Seven-segment display patterns.
+//!
+//! Segments are named `a` to `g` clockwise from the top, with `g` in the
+//! middle. A [`SevenSegment`] maps each segment to a bit of an 8-bit port,
+//! leaving one bit free for the decimal point, and selects whether a segment
+//! is lit by a set bit (common cathode) or a cleared bit (common anode).
+//!
+//! ```
+//! use nanobv::{segment::SevenSegment, NanoBV};
+//!
+//! let cathode = SevenSegment::GFEDCBA;
+//! assert_eq!(NanoBV::<u8>::to_seven_segment(7, cathode), Some(NanoBV::<u8>::new(0b0000_0111, 8)));
+//!
+//! let anode = SevenSegment::new([7, 6, 5, 4, 3, 2, 1]).common_anode();
+//! let pattern = NanoBV::<u8>::to_seven_segment(0xC, anode).unwrap();
+//! assert_eq!(pattern, NanoBV::<u8>::new(0b0110_0011, 8));
+//! assert_eq!(pattern.from_seven_segment(anode), Some(0xC));
+//! ```
+
+use crate::NanoBV;
+
+/// Segments lit for each hexadecimal digit, with segment `a` at bit 0.
+const DIGITS: [u8; 16] = [
+    0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F, 0x77, 0x7C,
+    0x39, 0x5E, 0x79, 0x71,
+];
+
+/// Wiring of a seven-segment display to an 8-bit port.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct SevenSegment {
+    order: [u8; 7],
+    inverted: bool,
+}
+
+impl SevenSegment {
+    /// Common-cathode display with segment `a` at bit 0 and `g` at bit 6.
+    pub const GFEDCBA: SevenSegment = SevenSegment::new([0, 1, 2, 3, 4, 5, 6]);
+
+    /// Common-cathode display with segment `a` at bit 6 and `g` at bit 0.
+    pub const ABCDEFG: SevenSegment = SevenSegment::new([6, 5, 4, 3, 2, 1, 0]);
+
+    /// Create a common-cathode [`SevenSegment`] with segment `a + i` wired to
+    /// bit `order[i]`.
+    pub const fn new(order: [u8; 7]) -> Self {
+        let (mut used, mut i) = (0u8, 0);
+        while i < order.len() {
+            assert!(
+                order[i] < 8 && (used >> order[i]) & 1 == 0,
+                "Invalid order provided."
+            );
+            used |= 1 << order[i];
+            i += 1;
+        }
+        SevenSegment { order, inverted: false }
+    }
+
+    /// Light segments with a cleared bit instead, as for a common-anode
+    /// display. Unused bits are then set.
+    pub const fn common_anode(self) -> Self {
+        SevenSegment { order: self.order, inverted: true }
+    }
+
+    /// Map segments with `a` at bit 0 to port bits.
+    const fn port_bits(self, segments: u8) -> u8 {
+        let (mut port, mut i) = (0, 0);
+        while i < self.order.len() {
+            port |= ((segments >> i) & 1) << self.order[i];
+            i += 1;
+        }
+        match self.inverted {
+            true => !port,
+            false => port,
+        }
+    }
+
+    /// Map port bits to segments with `a` at bit 0, ignoring unused bits.
+    const fn segments(self, port: u8) -> u8 {
+        let port = match self.inverted {
+            true => !port,
+            false => port,
+        };
+        let (mut segments, mut i) = (0, 0);
+        while i < self.order.len() {
+            segments |= ((port >> self.order[i]) & 1) << i;
+            i += 1;
+        }
+        segments
+    }
+}
+
+impl NanoBV<u8> {
+    /// Create the 8-bit port value lighting hexadecimal `digit` on `display`,
+    /// or `None` if `digit` is above 15.
+    pub const fn to_seven_segment(
+        digit: u8,
+        display: SevenSegment,
+    ) -> Option<Self> {
+        match digit {
+            0..=15 => Some(NanoBV::<u8>::new(
+                display.port_bits(DIGITS[digit as usize]),
+                8,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Recover the hexadecimal digit shown by the current port value on
+    /// `display`, or `None` if the lit segments form no digit.
+    pub const fn from_seven_segment(
+        &self,
+        display: SevenSegment,
+    ) -> Option<u8> {
+        let segments = display.segments(self.data);
+        let mut digit = 0;
+        while digit < DIGITS.len() {
+            if DIGITS[digit] == segments {
+                return Some(digit as u8);
+            }
+            digit += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seven_segment() {
+        let display = SevenSegment::GFEDCBA;
+        for digit in 0..16 {
+            let pattern =
+                NanoBV::<u8>::to_seven_segment(digit, display).unwrap();
+            assert_eq!(pattern.from_seven_segment(display), Some(digit));
+            assert_eq!(
+                pattern.set_bit(7).from_seven_segment(display),
+                Some(digit)
+            );
+        }
+        assert_eq!(
+            NanoBV::<u8>::to_seven_segment(8, display),
+            Some(NanoBV::<u8>::new(0x7F, 8))
+        );
+        assert_eq!(NanoBV::<u8>::to_seven_segment(16, display), None);
+        assert_eq!(
+            NanoBV::<u8>::new(0b0100_0000, 8).from_seven_segment(display),
+            None
+        );
+    }
+
+    #[test]
+    fn test_seven_segment_wiring() {
+        let display = SevenSegment::ABCDEFG;
+        assert_eq!(
+            NanoBV::<u8>::to_seven_segment(1, display),
+            Some(NanoBV::<u8>::new(0b0011_0000, 8))
+        );
+        let anode = display.common_anode();
+        assert_eq!(
+            NanoBV::<u8>::to_seven_segment(1, anode),
+            Some(NanoBV::<u8>::new(0b1100_1111, 8))
+        );
+        assert_eq!(
+            NanoBV::<u8>::new(0b1100_1111, 8).from_seven_segment(anode),
+            Some(1)
+        );
+        assert_eq!(
+            NanoBV::<u8>::new(0b0000_0001, 8).from_seven_segment(display),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_seven_segment_invalid_order() {
+        SevenSegment::new([0, 1, 2, 3, 4, 5, 5]);
+    }
+}
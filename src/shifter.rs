@@ -0,0 +1,143 @@
+//! ARM-style barrel shifter with carry-out.
+//!
+//! Each operation returns the shifted value together with the shifter carry
+//! out, computed for the declared length. A shift by zero leaves the value
+//! unchanged and passes `carry_in` through.
+
+use crate::NanoBV;
+
+macro_rules! ImplNanoBVShifter {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVShifter!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            const fn shifter_bit(&self, offset: usize) -> bool {
+                (self.data >> offset) & 1 == 1
+            }
+
+            /// Logical shift left by `amount`, returning the carry out.
+            pub const fn lsl_c(&self, amount: usize, carry_in: bool) -> (Self, bool) {
+                match amount {
+                0 => (*self, carry_in),
+                n if n < self.len() => (self.set_value(self.data << n), self.shifter_bit(self.len() - n)),
+                n if n == self.len() => (self.clear(), self.shifter_bit(0)),
+                _ => (self.clear(), false),
+                }
+            }
+
+            /// Logical shift right by `amount`, returning the carry out.
+            pub const fn lsr_c(&self, amount: usize, carry_in: bool) -> (Self, bool) {
+                match amount {
+                0 => (*self, carry_in),
+                n if n < self.len() => (self.set_value(self.data >> n), self.shifter_bit(n - 1)),
+                n if n == self.len() => (self.clear(), self.shifter_bit(n - 1)),
+                _ => (self.clear(), false),
+                }
+            }
+
+            /// Arithmetic shift right by `amount`, returning the carry out.
+            pub const fn asr_c(&self, amount: usize, carry_in: bool) -> (Self, bool) {
+                let msb = self.shifter_bit(self.len() - 1);
+                match amount {
+                0 => (*self, carry_in),
+                n if n < self.len() => {
+                    let fill = match msb {
+                    true => !(Self::upper_bound(self.length) >> n),
+                    false => 0,
+                    };
+                    (self.set_value((self.data >> n) | fill), self.shifter_bit(n - 1))
+                }
+                _ => match msb {
+                true => (self.set(), true),
+                false => (self.clear(), false),
+                },
+                }
+            }
+
+            /// Rotate right by `amount`, returning the carry out.
+            pub const fn ror_c(&self, amount: usize, carry_in: bool) -> (Self, bool) {
+                match $crate::internals::reduce(amount, self.len()) {
+                _ if amount == 0 => (*self, carry_in),
+                0 => (*self, self.shifter_bit(self.len() - 1)),
+                n => {
+                    let rotated = self.set_value((self.data >> n) | (self.data << (self.len() - n)));
+                    (rotated, rotated.shifter_bit(self.len() - 1))
+                }
+                }
+            }
+
+            /// Rotate right by one through `carry_in`, returning the carry out.
+            pub const fn rrx_c(&self, carry_in: bool) -> (Self, bool) {
+                let top = (carry_in as $type) << (self.len() - 1);
+                (self.set_value((self.data >> 1) | top), self.shifter_bit(0))
+            }
+        }
+    };
+}
+
+ImplNanoBVShifter!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplNanoBVShifterTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVShifterTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_lsl_lsr_c_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1001_0110, 8);
+                assert_eq!(bv.lsl_c(0, true), (bv, true));
+                assert_eq!(bv.lsl_c(1, false), (NBV::new(0b0010_1100, 8), true));
+                assert_eq!(bv.lsl_c(2, true), (NBV::new(0b0101_1000, 8), false));
+                assert_eq!(bv.lsl_c(8, true), (NBV::zeros(8), false));
+                assert_eq!(NBV::new(1, 8).lsl_c(8, false), (NBV::zeros(8), true));
+                assert_eq!(bv.lsl_c(9, true), (NBV::zeros(8), false));
+                assert_eq!(bv.lsr_c(2, false), (NBV::new(0b0010_0101, 8), true));
+                assert_eq!(bv.lsr_c(1, true), (NBV::new(0b0100_1011, 8), false));
+                assert_eq!(bv.lsr_c(8, false), (NBV::zeros(8), true));
+                assert_eq!(bv.lsr_c(33, true), (NBV::zeros(8), false));
+                let full = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(full.lsl_c(NBV::BIT_SIZE - 1, false), (NBV::new(1 << (NBV::BIT_SIZE - 1), NBV::BIT_SIZE), true));
+            }
+
+            #[test]
+            fn [<test_nanobv_asr_c_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1001_0110, 8);
+                assert_eq!(bv.asr_c(0, false), (bv, false));
+                assert_eq!(bv.asr_c(2, false), (NBV::new(0b1110_0101, 8), true));
+                assert_eq!(bv.asr_c(8, false), (NBV::ones(8), true));
+                assert_eq!(bv.asr_c(40, false), (NBV::ones(8), true));
+                assert_eq!(NBV::new(0b0101, 4).asr_c(1, false), (NBV::new(0b0010, 4), true));
+                assert_eq!(NBV::new(0b0101, 4).asr_c(4, true), (NBV::zeros(4), false));
+            }
+
+            #[test]
+            fn [<test_nanobv_ror_rrx_c_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1001_0110, 8);
+                assert_eq!(bv.ror_c(0, true), (bv, true));
+                assert_eq!(bv.ror_c(2, false), (NBV::new(0b1010_0101, 8), true));
+                assert_eq!(bv.ror_c(3, true), (NBV::new(0b1101_0010, 8), true));
+                assert_eq!(bv.ror_c(1, true), (NBV::new(0b0100_1011, 8), false));
+                assert_eq!(bv.ror_c(16, false), (bv, true));
+                assert_eq!(bv.ror_c(usize::MAX, true), (NBV::new(0b0010_1101, 8), false));
+                assert_eq!(NBV::new(0b10110, 5).ror_c(usize::MAX, false), (NBV::new(0b10110, 5), true));
+                assert_eq!(bv.rrx_c(true), (NBV::new(0b1100_1011, 8), false));
+                assert_eq!(NBV::new(0b0011, 4).rrx_c(false), (NBV::new(0b0001, 4), true));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVShifterTest!(for u8, u16, u32, u64);
+}
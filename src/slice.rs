@@ -0,0 +1,114 @@
+//! Batch operations over slices of NanoBVs.
+//!
+//! Each function is a single pass over zipped slices with no early exits, so
+//! the compiler is free to vectorize it. Element pairs follow the usual
+//! mixed-length policy of the underlying operation.
+
+use crate::NanoBV;
+use core::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
+
+/// AND each element of `src` into the corresponding element of `dst`.
+///
+/// Panics if the slices differ in length.
+pub fn and_slices<T>(dst: &mut [NanoBV<T>], src: &[NanoBV<T>])
+where
+    NanoBV<T>: BitAndAssign + Copy,
+{
+    assert_eq!(dst.len(), src.len(), "Invalid length provided.");
+    for (dst, src) in dst.iter_mut().zip(src) {
+        *dst &= *src;
+    }
+}
+
+/// OR each element of `src` into the corresponding element of `dst`.
+///
+/// Panics if the slices differ in length.
+pub fn or_slices<T>(dst: &mut [NanoBV<T>], src: &[NanoBV<T>])
+where
+    NanoBV<T>: BitOrAssign + Copy,
+{
+    assert_eq!(dst.len(), src.len(), "Invalid length provided.");
+    for (dst, src) in dst.iter_mut().zip(src) {
+        *dst |= *src;
+    }
+}
+
+/// XOR each element of `src` into the corresponding element of `dst`.
+///
+/// Panics if the slices differ in length.
+pub fn xor_slices<T>(dst: &mut [NanoBV<T>], src: &[NanoBV<T>])
+where
+    NanoBV<T>: BitXorAssign + Copy,
+{
+    assert_eq!(dst.len(), src.len(), "Invalid length provided.");
+    for (dst, src) in dst.iter_mut().zip(src) {
+        *dst ^= *src;
+    }
+}
+
+/// Count the set bits of all elements of `src`.
+pub fn popcount_slice<T: Into<u64> + Copy>(src: &[NanoBV<T>]) -> usize {
+    src.iter().map(|bv| bv.data.into().count_ones() as usize).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_and_slices() {
+        let mut dst = [NanoBV::<u8>::new(0b1100, 4), NanoBV::<u8>::ones(8)];
+        and_slices(
+            &mut dst,
+            &[NanoBV::<u8>::new(0b1010, 4), NanoBV::<u8>::new(0x5A, 8)],
+        );
+        assert_eq!(
+            dst,
+            [NanoBV::<u8>::new(0b1000, 4), NanoBV::<u8>::new(0x5A, 8)]
+        );
+    }
+
+    #[test]
+    fn test_or_slices() {
+        let mut dst = [NanoBV::<u16>::new(0b1100, 4), NanoBV::<u16>::zeros(16)];
+        or_slices(
+            &mut dst,
+            &[NanoBV::<u16>::new(0b1010, 4), NanoBV::<u16>::new(0x5A5A, 16)],
+        );
+        assert_eq!(
+            dst,
+            [NanoBV::<u16>::new(0b1110, 4), NanoBV::<u16>::new(0x5A5A, 16)]
+        );
+    }
+
+    #[test]
+    fn test_xor_slices() {
+        let mut dst = [NanoBV::<u32>::new(0b1100, 4), NanoBV::<u32>::ones(32)];
+        xor_slices(
+            &mut dst,
+            &[NanoBV::<u32>::new(0b1010, 4), NanoBV::<u32>::ones(32)],
+        );
+        assert_eq!(
+            dst,
+            [NanoBV::<u32>::new(0b0110, 4), NanoBV::<u32>::zeros(32)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_xor_slices_length_mismatch() {
+        let mut dst = [NanoBV::<u64>::ones(64); 2];
+        xor_slices(&mut dst, &[NanoBV::<u64>::ones(64)]);
+    }
+
+    #[test]
+    fn test_popcount_slice() {
+        let src = [
+            NanoBV::<u64>::ones(64),
+            NanoBV::<u64>::new(0b1011, 4),
+            NanoBV::<u64>::zeros(3),
+        ];
+        assert_eq!(popcount_slice(&src), 67);
+        assert_eq!(popcount_slice::<u8>(&[]), 0);
+    }
+}
@@ -0,0 +1,479 @@
+//! SMT-LIB fixed-size bitvector operations.
+//!
+//! Together with the `bv*` methods on [`NanoBV`] these cover the operations
+//! of the SMT-LIB `FixedSizeBitVectors` theory and its `QF_BV` extensions,
+//! following the standard's definitions (including division by zero).
+//! Operands of differing lengths are truncated to the shorter length, as with
+//! the other binary operations of this crate.
+//!
+//! Literals can be exchanged with solvers through [`NanoBV::to_smt2`] and
+//! [`NanoBV::from_smt2`].
+//!
+//! [`NanoBV::bvadd`], [`NanoBV::bvsub`] and [`NanoBV::bvmul`] wrap in the
+//! backing type and reduce modulo the declared length, so they never panic.
+
+use crate::NanoBV;
+#[cfg(feature = "fmt")]
+use crate::{
+    internals::{parse_decimal, parse_digits, write_digits},
+    Error,
+};
+
+macro_rules! ImplNanoBVSmt {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVSmt!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            const fn smt_operands(&self, rhs: Self) -> (Self, Self) {
+                let length = $crate::internals::min(self.len(), rhs.len());
+                (NanoBV::<$type>::new(self.data, length), NanoBV::<$type>::new(rhs.data, length))
+            }
+
+            const fn smt_msb(&self) -> bool {
+                (self.data >> (self.len() - 1)) & 1 == 1
+            }
+
+            #[cfg(feature = "div")]
+            const fn smt_abs(&self) -> Self {
+                match self.smt_msb() {
+                true => self.bvneg(),
+                false => *self,
+                }
+            }
+
+            /// SMT-LIB `bvnot`.
+            pub const fn bvnot(&self) -> Self {
+                NanoBV::<$type>::new(!self.data, self.len())
+            }
+
+            /// SMT-LIB `bvneg`.
+            pub const fn bvneg(&self) -> Self {
+                NanoBV::<$type>::new(self.data.wrapping_neg(), self.len())
+            }
+
+            /// SMT-LIB `bvnand`.
+            pub const fn bvnand(&self, rhs: Self) -> Self {
+                self.bvand(rhs).bvnot()
+            }
+
+            /// SMT-LIB `bvnor`.
+            pub const fn bvnor(&self, rhs: Self) -> Self {
+                self.bvor(rhs).bvnot()
+            }
+
+            /// SMT-LIB `bvxnor`.
+            pub const fn bvxnor(&self, rhs: Self) -> Self {
+                self.bvxor(rhs).bvnot()
+            }
+
+            /// SMT-LIB `bvcomp`, a single bit set when both operands are equal.
+            pub const fn bvcomp(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                NanoBV::<$type>::new((lhs.data == rhs.data) as $type, 1)
+            }
+
+            /// SMT-LIB `bvudiv`, yielding all ones when dividing by zero.
+            #[cfg(feature = "div")]
+            pub const fn bvudiv(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                match rhs.data {
+                0 => lhs.set(),
+                _ => NanoBV::<$type>::new(lhs.data / rhs.data, lhs.len()),
+                }
+            }
+
+            /// SMT-LIB `bvurem`, yielding the dividend when dividing by zero.
+            #[cfg(feature = "div")]
+            pub const fn bvurem(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                match rhs.data {
+                0 => lhs,
+                _ => NanoBV::<$type>::new(lhs.data % rhs.data, lhs.len()),
+                }
+            }
+
+            /// SMT-LIB `bvsdiv`.
+            #[cfg(feature = "div")]
+            pub const fn bvsdiv(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                let quotient = lhs.smt_abs().bvudiv(rhs.smt_abs());
+                match lhs.smt_msb() != rhs.smt_msb() {
+                true => quotient.bvneg(),
+                false => quotient,
+                }
+            }
+
+            /// SMT-LIB `bvsrem`, taking the sign of the dividend.
+            #[cfg(feature = "div")]
+            pub const fn bvsrem(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                let remainder = lhs.smt_abs().bvurem(rhs.smt_abs());
+                match lhs.smt_msb() {
+                true => remainder.bvneg(),
+                false => remainder,
+                }
+            }
+
+            /// SMT-LIB `bvsmod`, taking the sign of the divisor.
+            #[cfg(feature = "div")]
+            pub const fn bvsmod(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                let remainder = lhs.smt_abs().bvurem(rhs.smt_abs());
+                match (remainder.data, lhs.smt_msb(), rhs.smt_msb()) {
+                (0, _, _) | (_, false, false) => remainder,
+                (_, true, false) => NanoBV::<$type>::new(remainder.bvneg().data.wrapping_add(rhs.data), lhs.len()),
+                (_, false, true) => NanoBV::<$type>::new(remainder.data.wrapping_add(rhs.data), lhs.len()),
+                (_, true, true) => remainder.bvneg(),
+                }
+            }
+
+            /// SMT-LIB `bvlshr`, yielding zero for shifts of at least the length.
+            pub const fn bvlshr(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                match rhs.data as u64 >= lhs.len() as u64 {
+                true => lhs.clear(),
+                false => NanoBV::<$type>::new(lhs.data >> rhs.data, lhs.len()),
+                }
+            }
+
+            /// SMT-LIB `bvashr`, replicating the sign bit.
+            pub const fn bvashr(&self, rhs: Self) -> Self {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                match lhs.smt_msb() {
+                true => lhs.bvnot().bvlshr(rhs).bvnot(),
+                false => lhs.bvlshr(rhs),
+                }
+            }
+
+            /// SMT-LIB `bvult`.
+            pub const fn bvult(&self, rhs: Self) -> bool {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                lhs.data < rhs.data
+            }
+
+            /// SMT-LIB `bvule`.
+            pub const fn bvule(&self, rhs: Self) -> bool {
+                !rhs.bvult(*self)
+            }
+
+            /// SMT-LIB `bvugt`.
+            pub const fn bvugt(&self, rhs: Self) -> bool {
+                rhs.bvult(*self)
+            }
+
+            /// SMT-LIB `bvuge`.
+            pub const fn bvuge(&self, rhs: Self) -> bool {
+                !self.bvult(rhs)
+            }
+
+            /// SMT-LIB `bvslt`.
+            pub const fn bvslt(&self, rhs: Self) -> bool {
+                let (lhs, rhs) = self.smt_operands(rhs);
+                lhs.signed_value() < rhs.signed_value()
+            }
+
+            /// SMT-LIB `bvsle`.
+            pub const fn bvsle(&self, rhs: Self) -> bool {
+                !rhs.bvslt(*self)
+            }
+
+            /// SMT-LIB `bvsgt`.
+            pub const fn bvsgt(&self, rhs: Self) -> bool {
+                rhs.bvslt(*self)
+            }
+
+            /// SMT-LIB `bvsge`.
+            pub const fn bvsge(&self, rhs: Self) -> bool {
+                !self.bvslt(rhs)
+            }
+
+            /// SMT-LIB `(_ rotate_left i)`, rotating within the declared length.
+            pub const fn rotate_left(&self, amount: usize) -> Self {
+                let amount = $crate::internals::reduce(amount, self.len());
+                match amount {
+                0 => *self,
+                _ => NanoBV::<$type>::new((self.data << amount) | (self.data >> (self.len() - amount)), self.len()),
+                }
+            }
+
+            /// SMT-LIB `(_ rotate_right i)`, rotating within the declared length.
+            pub const fn rotate_right(&self, amount: usize) -> Self {
+                self.rotate_left(self.len() - $crate::internals::reduce(amount, self.len()))
+            }
+
+            /// SMT-LIB `concat`, placing `self` above `rhs`.
+            pub const fn concat(&self, rhs: Self) -> Self {
+                let length = self.len() + rhs.len();
+                ["Invalid length provided."][(length > Self::BIT_SIZE) as usize];
+                let high = match rhs.len() {
+                n if n < Self::BIT_SIZE => self.data << n,
+                _ => 0,
+                };
+                NanoBV::<$type>::new(high | rhs.data, length)
+            }
+
+            /// SMT-LIB `(_ extract i j)`, retrieving bits `i` down to `j` inclusive.
+            pub const fn extract(&self, i: usize, j: usize) -> Self {
+                ["Invalid offset provided."][((i >= self.len()) || (j > i)) as usize];
+                NanoBV::<$type>::new(self.data >> j, i - j + 1)
+            }
+
+            /// SMT-LIB `(_ zero_extend i)`, widening by `i` zero bits.
+            pub const fn zero_extend(&self, i: usize) -> Self {
+                NanoBV::<$type>::new(self.data, self.len() + i)
+            }
+
+            /// SMT-LIB `(_ sign_extend i)`, widening by `i` copies of the sign bit.
+            pub const fn sign_extend(&self, i: usize) -> Self {
+                NanoBV::<$type>::new(self.signed_value() as $type, self.len() + i)
+            }
+
+            /// SMT-LIB `(_ repeat i)`, concatenating `i` copies of `self`.
+            pub const fn repeat(&self, i: usize) -> Self {
+                ["Invalid length provided."][(i < 1) as usize];
+                let mut repeated = *self;
+                let mut count = 1;
+                while count < i {
+                    repeated = repeated.concat(*self);
+                    count += 1;
+                }
+                repeated
+            }
+
+            /// Write the SMT-LIB literal of the current NanoBV into `buf`,
+            /// returning the number of bytes written.
+            ///
+            /// Lengths that are a multiple of four are written in hexadecimal
+            /// (`#x...`), all others in binary (`#b...`).
+            #[cfg(feature = "fmt")]
+            pub fn to_smt2(&self, buf: &mut [u8]) -> Result<usize, Error> {
+                let (prefix, radix_bits) = match self.len() % 4 {
+                0 => (b'x', 4),
+                _ => (b'b', 1),
+                };
+                match buf {
+                [hash, radix, digits @ ..] => {
+                    *hash = b'#';
+                    *radix = prefix;
+                    Ok(2 + write_digits(self.data as u64, self.len() / radix_bits, radix_bits, digits)?)
+                }
+                _ => Err(Error::BufferTooSmall),
+                }
+            }
+
+            /// Parse an SMT-LIB literal (`#b...`, `#x...` or `(_ bvN W)`).
+            #[cfg(feature = "fmt")]
+            pub const fn from_smt2(literal: &str) -> Result<Self, Error> {
+                let bytes = literal.as_bytes();
+                if bytes.len() > 1 && bytes[0] == b'#' {
+                    let radix_bits = match bytes[1] {
+                    b'b' => 1,
+                    b'x' => 4,
+                    _ => return Err(Error::InvalidFormat),
+                    };
+                    let length = (bytes.len() - 2) * radix_bits;
+                    if length > Self::BIT_SIZE {
+                        return Err(Error::InvalidLength);
+                    }
+                    return match parse_digits(bytes, 2, radix_bits) {
+                    Ok(value) => Ok(NanoBV::<$type>::new(value as $type, length)),
+                    Err(error) => Err(error),
+                    };
+                }
+                Self::from_smt2_indexed(bytes)
+            }
+
+            #[cfg(feature = "fmt")]
+            const fn from_smt2_indexed(bytes: &[u8]) -> Result<Self, Error> {
+                let prefix = b"(_ bv";
+                let mut i = 0;
+                while i < prefix.len() {
+                    if i >= bytes.len() || bytes[i] != prefix[i] {
+                        return Err(Error::InvalidFormat);
+                    }
+                    i += 1;
+                }
+                let (value, mut i) = match parse_decimal(bytes, i) {
+                Ok(parsed) => parsed,
+                Err(error) => return Err(error),
+                };
+                let start = i;
+                while i < bytes.len() && bytes[i] == b' ' {
+                    i += 1;
+                }
+                if i == start {
+                    return Err(Error::InvalidFormat);
+                }
+                let (length, i) = match parse_decimal(bytes, i) {
+                Ok(parsed) => parsed,
+                Err(error) => return Err(error),
+                };
+                if i + 1 != bytes.len() || bytes[i] != b')' {
+                    return Err(Error::InvalidFormat);
+                }
+                if length < 1 || length > Self::BIT_SIZE as u64 {
+                    return Err(Error::InvalidLength);
+                }
+                if length < 64 && value >> length != 0 {
+                    return Err(Error::Overflow);
+                }
+                Ok(NanoBV::<$type>::new(value as $type, length as usize))
+            }
+        }
+    };
+}
+
+ImplNanoBVSmt!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVSmtTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVSmtTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_bvneg_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE - 1);
+                assert_eq!(bv.bvneg().value().wrapping_add(bv.value()) & (NBV::ones(NBV::BIT_SIZE - 1).value()), 0);
+                assert_eq!(bv.bvnot().bvnot(), bv);
+            }
+
+            #[test]
+            #[cfg(feature = "div")]
+            fn [<test_nanobv_bvudiv_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(13, 8);
+                assert_eq!(bv.bvudiv(NBV::new(4, 8)), NBV::new(3, 8));
+                assert_eq!(bv.bvurem(NBV::new(4, 8)), NBV::new(1, 8));
+                assert_eq!(bv.bvudiv(NBV::zeros(8)), NBV::ones(8));
+                assert_eq!(bv.bvurem(NBV::zeros(8)), bv);
+            }
+
+            #[test]
+            #[cfg(feature = "div")]
+            fn [<test_nanobv_bvsdiv_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (minus_seven, two) = (NBV::new(-7i8 as $type, 8), NBV::new(2, 8));
+                assert_eq!(minus_seven.bvsdiv(two).signed_value(), -3);
+                assert_eq!(minus_seven.bvsrem(two).signed_value(), -1);
+                assert_eq!(minus_seven.bvsmod(two).signed_value(), 1);
+                assert_eq!(two.bvsmod(NBV::new(-3i8 as $type, 8)).signed_value(), -1);
+                assert_eq!(minus_seven.bvsdiv(NBV::zeros(8)).signed_value(), 1);
+                assert_eq!(two.bvsdiv(NBV::zeros(8)).signed_value(), -1);
+            }
+
+            #[test]
+            fn [<test_nanobv_bvshift_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b1000_0100, 8);
+                assert_eq!(bv.bvlshr(NBV::new(2, 8)), NBV::new(0b0010_0001, 8));
+                assert_eq!(bv.bvashr(NBV::new(2, 8)), NBV::new(0b1110_0001, 8));
+                assert_eq!(bv.bvlshr(NBV::new(8, 8)), NBV::zeros(8));
+                assert_eq!(bv.bvashr(NBV::new(200, 8)), NBV::ones(8));
+                assert_eq!(bv.bvshl(NBV::new(2, 8)), NBV::new(0b0001_0000, 8));
+                assert_eq!(NBV::new(1, 8).bvshl(NBV::new(7, 8)), NBV::new(0x80, 8));
+                assert_eq!(NBV::new(1, 8).bvshl(NBV::new(8, 8)), NBV::zeros(8));
+                assert_eq!(NBV::new(1, 8) << NBV::new(9, 8), NBV::zeros(8));
+                assert_eq!(NBV::ones(NBV::BIT_SIZE).bvshl(NBV::new(<$type>::MAX, NBV::BIT_SIZE)), NBV::zeros(NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_bvcompare_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let (minus_one, one) = (NBV::ones(8), NBV::new(1, 8));
+                assert!(one.bvult(minus_one) && one.bvsgt(minus_one));
+                assert!(one.bvule(one) && one.bvsge(one));
+                assert_eq!(one.bvcomp(one), NBV::ones(1));
+                assert_eq!(one.bvcomp(minus_one), NBV::zeros(1));
+            }
+
+            #[test]
+            fn [<test_nanobv_rotate_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let data = rng.generate();
+                let bv = NBV::new(data, NBV::BIT_SIZE);
+                assert_eq!(bv.rotate_left(3).value(), data.rotate_left(3));
+                assert_eq!(bv.rotate_right(3).value(), data.rotate_right(3));
+                assert_eq!(NBV::new(0b1001, 4).rotate_left(1), NBV::new(0b0011, 4));
+                assert_eq!(NBV::new(0b1001, 4).rotate_right(9), NBV::new(0b1100, 4));
+                assert_eq!(NBV::new(1, 3).rotate_left(10_000_000_000), NBV::new(0b010, 3));
+                assert_eq!(NBV::new(1, 3).rotate_right(usize::MAX), NBV::new(1, 3));
+                assert_eq!(NBV::new(1, 7).rotate_left(usize::MAX), NBV::new(0b10, 7));
+            }
+
+            #[test]
+            fn [<test_nanobv_bv_arith_wrap_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let max = NBV::ones(NBV::BIT_SIZE);
+                assert_eq!(max.bvadd(NBV::new(1, NBV::BIT_SIZE)), NBV::zeros(NBV::BIT_SIZE));
+                assert_eq!(NBV::zeros(8).bvsub(NBV::new(1, 8)), NBV::new(0xFF, 8));
+                assert_eq!(max.bvmul(max), NBV::new(1, NBV::BIT_SIZE));
+            }
+
+            #[test]
+            fn [<test_nanobv_concat_extract_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let bv = NBV::new(0b101, 3).concat(NBV::new(0b01, 2));
+                assert_eq!(bv, NBV::new(0b10101, 5));
+                assert_eq!(bv.extract(3, 1), NBV::new(0b010, 3));
+                assert_eq!(NBV::new(0b10, 2).repeat(3), NBV::new(0b101010, 6));
+                assert_eq!(NBV::new(0b101, 3).zero_extend(2), NBV::new(0b00101, 5));
+                assert_eq!(NBV::new(0b101, 3).sign_extend(2), NBV::new(0b11101, 5));
+            }
+
+            #[test]
+            #[cfg(feature = "fmt")]
+            fn [<test_nanobv_to_smt2_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut buf = [0u8; 66];
+                let written = NBV::new(0xA5, 8).to_smt2(&mut buf).unwrap();
+                assert_eq!(&buf[..written], b"#xa5");
+                let written = NBV::new(0b101, 5).to_smt2(&mut buf).unwrap();
+                assert_eq!(&buf[..written], b"#b00101");
+                let written = NBV::ones(NBV::BIT_SIZE).to_smt2(&mut buf).unwrap();
+                assert_eq!(NBV::from_smt2(core::str::from_utf8(&buf[..written]).unwrap()), Ok(NBV::ones(NBV::BIT_SIZE)));
+                assert_eq!(NBV::new(0xA5, 8).to_smt2(&mut buf[..3]), Err(Error::BufferTooSmall));
+            }
+
+            #[test]
+            #[cfg(feature = "fmt")]
+            fn [<test_nanobv_from_smt2_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::from_smt2("#b00101"), Ok(NBV::new(0b101, 5)));
+                assert_eq!(NBV::from_smt2("#xA5"), Ok(NBV::new(0xA5, 8)));
+                assert_eq!(NBV::from_smt2("(_ bv13 7)"), Ok(NBV::new(13, 7)));
+                assert_eq!(NBV::from_smt2("#b0120"), Err(Error::InvalidDigit));
+                assert_eq!(NBV::from_smt2("#o17"), Err(Error::InvalidFormat));
+                assert_eq!(NBV::from_smt2("#x"), Err(Error::InvalidFormat));
+                assert_eq!(NBV::from_smt2("(_ bv13 3)"), Err(Error::Overflow));
+                assert_eq!(NBV::from_smt2("(_ bv13 0)"), Err(Error::InvalidLength));
+                assert_eq!(NBV::from_smt2("(_ bv13 7"), Err(Error::InvalidFormat));
+                assert_eq!(NBV::from_smt2("#x00000000000000000"), Err(Error::InvalidLength));
+                assert_eq!(parse_digits(b"#x10000000000000000", 2, 4), Err(Error::Overflow));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVSmtTest!(for u8, u16, u32, u64);
+
+    #[test]
+    fn test_nanobv_bvadd_wrap_u8() {
+        assert_eq!(
+            NanoBV::<u8>::new(0xFF, 8).bvadd(NanoBV::<u8>::new(1, 8)),
+            NanoBV::<u8>::new(0, 8)
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Integration with svd2rust register field readers and writers.
+//!
+//! svd2rust generates its `FieldReader`/`FieldWriter` types inside every
+//! peripheral access crate, so this crate cannot implement anything for them
+//! directly. Instead, [`FieldRead`] and [`FieldWrite`] describe the parts of
+//! those types used here, and [`impl_svd2rust_field!`](crate::impl_svd2rust_field)
+//! implements them in the crate that owns the field types.
+//!
+//! ```
+//! # mod pac {
+//! #     pub struct MODE_R(pub u8);
+//! #     impl MODE_R {
+//! #         pub fn bits(&self) -> u8 { self.0 }
+//! #     }
+//! # }
+//! use nanobv::NanoBV;
+//!
+//! nanobv::impl_svd2rust_field!(reader pac::MODE_R => u8, 3);
+//!
+//! # let reader = pac::MODE_R(0b101);
+//! let mode = NanoBV::<u8>::from_field_reader(&reader);
+//! assert_eq!(mode, NanoBV::<u8>::new(0b101, 3));
+//! ```
+
+use crate::NanoBV;
+
+/// Register field reader exposing the raw field bits.
+pub trait FieldRead {
+    /// Type holding the field bits.
+    type Ux;
+    /// Width of the field.
+    const WIDTH: usize;
+
+    /// Read the raw field bits.
+    fn read_bits(&self) -> Self::Ux;
+}
+
+/// Register field writer accepting the raw field bits.
+pub trait FieldWrite {
+    /// Type holding the field bits.
+    type Ux;
+    /// Value returned by the writer, usually the register writer.
+    type Output;
+    /// Width of the field.
+    const WIDTH: usize;
+
+    /// Write the raw field bits.
+    fn write_bits(self, bits: Self::Ux) -> Self::Output;
+}
+
+/// Implement [`FieldRead`](crate::svd::FieldRead) or
+/// [`FieldWrite`](crate::svd::FieldWrite) for an svd2rust field type,
+/// forwarding to its `bits` method.
+///
+/// Writers whose `bits` method is `unsafe` must be declared with
+/// `unsafe writer`, by which the invoker guarantees that every value of the
+/// declared width is valid for the field.
+#[macro_export]
+macro_rules! impl_svd2rust_field {
+    (reader $reader:ty => $ux:ty, $width:expr) => {
+        impl $crate::svd::FieldRead for $reader {
+            type Ux = $ux;
+            const WIDTH: usize = $width;
+
+            fn read_bits(&self) -> $ux {
+                <$reader>::bits(self)
+            }
+        }
+    };
+
+    (writer<$lifetime:lifetime> $writer:ty => $ux:ty, $output:ty, $width:expr) => {
+        impl<$lifetime> $crate::svd::FieldWrite for $writer {
+            type Ux = $ux;
+            type Output = $output;
+            const WIDTH: usize = $width;
+
+            fn write_bits(self, bits: $ux) -> $output {
+                <$writer>::bits(self, bits)
+            }
+        }
+    };
+
+    (unsafe writer<$lifetime:lifetime> $writer:ty => $ux:ty, $output:ty, $width:expr) => {
+        impl<$lifetime> $crate::svd::FieldWrite for $writer {
+            type Ux = $ux;
+            type Output = $output;
+            const WIDTH: usize = $width;
+
+            fn write_bits(self, bits: $ux) -> $output {
+                // SAFETY: guaranteed by the invoker of `unsafe writer`.
+                unsafe { <$writer>::bits(self, bits) }
+            }
+        }
+    };
+}
+
+macro_rules! ImplNanoBVSvd {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVSvd!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Create a [`NanoBV`] from an svd2rust field reader, using the field
+            /// width as the length.
+            pub fn from_field_reader<R: FieldRead<Ux = $type>>(reader: &R) -> Self {
+                NanoBV::<$type>::new(reader.read_bits(), R::WIDTH)
+            }
+
+            /// Write the current NanoBV to an svd2rust field writer.
+            pub fn write_to_field<W: FieldWrite<Ux = $type>>(&self, writer: W) -> W::Output {
+                ["Invalid length provided."][(self.len() > W::WIDTH) as usize];
+                writer.write_bits(self.data)
+            }
+        }
+    };
+}
+
+ImplNanoBVSvd!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ModeReader(u16);
+
+    impl ModeReader {
+        fn bits(&self) -> u16 {
+            self.0
+        }
+    }
+
+    struct RegisterWriter(u16);
+
+    struct ModeWriter<'a>(&'a mut RegisterWriter);
+
+    struct EnableWriter<'a>(&'a mut RegisterWriter);
+
+    impl<'a> EnableWriter<'a> {
+        fn bits(self, value: u16) -> &'a mut RegisterWriter {
+            self.0 .0 = (self.0 .0 & !0x1) | (value & 0x1);
+            self.0
+        }
+    }
+
+    impl<'a> ModeWriter<'a> {
+        unsafe fn bits(self, value: u16) -> &'a mut RegisterWriter {
+            self.0 .0 = (self.0 .0 & !0x3F0) | ((value << 4) & 0x3F0);
+            self.0
+        }
+    }
+
+    impl_svd2rust_field!(reader ModeReader => u16, 6);
+    impl_svd2rust_field!(unsafe writer<'a> ModeWriter<'a> => u16, &'a mut RegisterWriter, 6);
+    impl_svd2rust_field!(writer<'a> EnableWriter<'a> => u16, &'a mut RegisterWriter, 1);
+
+    #[test]
+    fn test_nanobv_from_field_reader() {
+        let mode = NanoBV::<u16>::from_field_reader(&ModeReader(0b10_1101));
+        assert_eq!(mode, NanoBV::<u16>::new(0b10_1101, 6));
+    }
+
+    #[test]
+    fn test_nanobv_write_to_field() {
+        let mut register = RegisterWriter(0xF00F);
+        NanoBV::<u16>::new(0b10_1101, 6)
+            .write_to_field(ModeWriter(&mut register))
+            .0 |= 1;
+        assert_eq!(register.0, 0xF2DF);
+        NanoBV::<u16>::new(0, 1).write_to_field(EnableWriter(&mut register));
+        assert_eq!(register.0, 0xF2DE);
+    }
+}
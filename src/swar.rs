@@ -0,0 +1,154 @@
+//! Lane-wise arithmetic within a register (SWAR).
+//!
+//! A `NanoBV<u32>` or `NanoBV<u64>` is read as packed unsigned lanes of 8 or
+//! 16 bits, lane 0 being the least significant. The length must be a whole
+//! number of lanes. Carries and borrows never cross lane boundaries, so each
+//! lane wraps on its own:
+//!
+//! ```
+//! use nanobv::NanoBV;
+//!
+//! let pixels = NanoBV::<u32>::new(0x10_FF_80_7F, 32);
+//! let offset = NanoBV::<u32>::new(0x01_01_01_01, 32);
+//! assert_eq!(pixels.lane_add(offset, 8), NanoBV::<u32>::new(0x11_00_81_80, 32));
+//! assert_eq!(pixels.lane_lt(offset.lane_add(offset, 8), 8), NanoBV::<u32>::new(0, 32));
+//! assert_eq!(pixels.lane_max(NanoBV::<u32>::new(0x20_20_20_20, 32), 8), NanoBV::<u32>::new(0x20_FF_80_7F, 32));
+//! ```
+
+use crate::NanoBV;
+
+macro_rules! ImplNanoBVSwar {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVSwar!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Mask of the most significant bit of each `width`-bit lane, after
+            /// checking the lane width against both lengths.
+            const fn swar_high(length: usize, rhs_length: usize, width: usize) -> $type {
+                ["Invalid width provided."][((width != 8) && (width != 16)) as usize];
+                ["Invalid length provided."][(((length & (width - 1)) != 0) || ((rhs_length & (width - 1)) != 0)) as usize];
+                match width {
+                8 => 0x8080_8080_8080_8080_u64 as $type,
+                _ => 0x8000_8000_8000_8000_u64 as $type,
+                }
+            }
+
+            /// Widen the most significant bit of each lane to the whole lane.
+            const fn swar_expand(high: $type, width: usize) -> $type {
+                (high >> (width - 1)) * ((1 << width) - 1)
+            }
+
+            /// Add `rhs` lane-wise, wrapping within each `width`-bit lane and within
+            /// the shorter length.
+            pub const fn lane_add(&self, rhs: Self, width: usize) -> Self {
+                let high = Self::swar_high(self.len(), rhs.len(), width);
+                let sum = ((self.data & !high) + (rhs.data & !high)) ^ ((self.data ^ rhs.data) & high);
+                NanoBV::<$type>::new(sum, $crate::internals::min(self.len(), rhs.len()))
+            }
+
+            /// Subtract `rhs` lane-wise, wrapping within each `width`-bit lane and
+            /// within the shorter length.
+            pub const fn lane_sub(&self, rhs: Self, width: usize) -> Self {
+                let high = Self::swar_high(self.len(), rhs.len(), width);
+                let difference = ((self.data | high) - (rhs.data & !high)) ^ ((self.data ^ !rhs.data) & high);
+                NanoBV::<$type>::new(difference, $crate::internals::min(self.len(), rhs.len()))
+            }
+
+            /// Compare lane-wise, setting every bit of the `width`-bit lanes equal to
+            /// those of `rhs`.
+            pub const fn lane_eq(&self, rhs: Self, width: usize) -> Self {
+                let high = Self::swar_high(self.len(), rhs.len(), width);
+                let diff = self.data ^ rhs.data;
+                let nonzero = (((diff & !high) + !high) | diff) & high;
+                NanoBV::<$type>::new(Self::swar_expand(!nonzero & high, width), $crate::internals::min(self.len(), rhs.len()))
+            }
+
+            /// Compare lane-wise as unsigned, setting every bit of the `width`-bit
+            /// lanes less than those of `rhs`.
+            pub const fn lane_lt(&self, rhs: Self, width: usize) -> Self {
+                let high = Self::swar_high(self.len(), rhs.len(), width);
+                let difference = self.lane_sub(rhs, width).data;
+                let borrow = ((!self.data & rhs.data) | (!(self.data ^ rhs.data) & difference)) & high;
+                NanoBV::<$type>::new(Self::swar_expand(borrow, width), $crate::internals::min(self.len(), rhs.len()))
+            }
+
+            /// Retrieve the unsigned minimum of each pair of `width`-bit lanes.
+            pub const fn lane_min(&self, rhs: Self, width: usize) -> Self {
+                let less = self.lane_lt(rhs, width).data;
+                NanoBV::<$type>::new((self.data & less) | (rhs.data & !less), $crate::internals::min(self.len(), rhs.len()))
+            }
+
+            /// Retrieve the unsigned maximum of each pair of `width`-bit lanes.
+            pub const fn lane_max(&self, rhs: Self, width: usize) -> Self {
+                let less = self.lane_lt(rhs, width).data;
+                NanoBV::<$type>::new((rhs.data & less) | (self.data & !less), $crate::internals::min(self.len(), rhs.len()))
+            }
+        }
+    };
+}
+
+ImplNanoBVSwar!(for u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVSwarTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVSwarTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_lane_ops_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let a = NBV::new(0x00_FF_80_7F, 32);
+                let b = NBV::new(0x01_01_80_FF, 32);
+                assert_eq!(a.lane_add(b, 8), NBV::new(0x01_00_00_7E, 32));
+                assert_eq!(a.lane_sub(b, 8), NBV::new(0xFF_FE_00_80, 32));
+                assert_eq!(a.lane_eq(b, 8), NBV::new(0x00_00_FF_00, 32));
+                assert_eq!(a.lane_lt(b, 8), NBV::new(0xFF_00_00_FF, 32));
+                assert_eq!(a.lane_min(b, 8), NBV::new(0x00_01_80_7F, 32));
+                assert_eq!(a.lane_max(b, 8), NBV::new(0x01_FF_80_FF, 32));
+                assert_eq!(a.lane_add(b, 16), NBV::new(0x0200_017E, 32));
+                assert_eq!(a.lane_lt(b, 16), NBV::new(0xFFFF_FFFF, 32));
+                assert_eq!(a.lane_add(NBV::new(0x01, 16), 8), NBV::new(0x8080, 16));
+            }
+
+            #[test]
+            fn [<test_nanobv_lane_ops_random_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                for width in [8, 16] {
+                    let (a, b) = (NBV::new(rng.generate(), NBV::BIT_SIZE), NBV::new(rng.generate(), NBV::BIT_SIZE));
+                    let (sum, difference, eq, lt) = (a.lane_add(b, width), a.lane_sub(b, width), a.lane_eq(b, width), a.lane_lt(b, width));
+                    let (min, max) = (a.lane_min(b, width), a.lane_max(b, width));
+                    let lane = |bv: NBV, i: usize| (bv.value() >> (i * width)) as u64 & ((1 << width) - 1);
+                    let ones = (1 << width) - 1;
+                    for i in 0..NBV::BIT_SIZE / width {
+                        let (x, y) = (lane(a, i), lane(b, i));
+                        assert_eq!(lane(sum, i), (x + y) & ones);
+                        assert_eq!(lane(difference, i), x.wrapping_sub(y) & ones);
+                        assert_eq!(lane(eq, i), [0, ones][(x == y) as usize]);
+                        assert_eq!(lane(lt, i), [0, ones][(x < y) as usize]);
+                        assert_eq!((lane(min, i), lane(max, i)), (x.min(y), x.max(y)));
+                    }
+                }
+            }
+
+            #[test]
+            #[should_panic]
+            fn [<test_nanobv_lane_ops_invalid_ $type>]() {
+                NanoBV::<$type>::new(0, 12).lane_add(NanoBV::<$type>::new(0, 12), 8);
+            }
+        }
+        };
+    }
+
+    ImplNanoBVSwarTest!(for u32, u64);
+}
@@ -0,0 +1,54 @@
+//! Fixed bit rearrangements resolved at compile time.
+//!
+//! ```
+//! use nanobv::{swizzle, NanoBV};
+//!
+//! const NIBBLE: NanoBV<u8> = NanoBV::<u8>::new(0b0001, 4);
+//! const REVERSED: NanoBV<u8> = swizzle!(NIBBLE; 3, 2, 1, 0);
+//!
+//! assert_eq!(REVERSED, NIBBLE.reverse());
+//! assert_eq!(swizzle!(NIBBLE; 0, 0, 0), NanoBV::<u8>::new(0b111, 3));
+//! ```
+
+/// Rearrange the bits of a [`NanoBV`](crate::NanoBV), taking output bit `i`
+/// from the `i`-th listed source offset, as in
+/// [`NanoBV::gather`](crate::NanoBV::gather).
+///
+/// The offsets are part of the expansion rather than a table, so with constant
+/// offsets each output bit compiles down to a shift and a mask. The length of
+/// the result is the number of offsets, and offsets may repeat.
+///
+/// The expansion only calls const fns, so it can be used in const contexts.
+#[macro_export]
+macro_rules! swizzle {
+    ($bv:expr; $first:expr $(, $source:expr)* $(,)?) => {{
+        let bv = $bv;
+        let swizzled = bv.extract($first, $first);
+        $(let swizzled = bv.extract($source, $source).concat(swizzled);)*
+        swizzled
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::NanoBV;
+
+    #[test]
+    fn test_swizzle() {
+        let bv = NanoBV::<u16>::new(0xA5F0, 16);
+        assert_eq!(
+            swizzle!(bv; 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0),
+            bv.reverse()
+        );
+        assert_eq!(
+            swizzle!(bv; 4, 15, 0, 0, 13,),
+            bv.gather(&[4, 15, 0, 0, 13])
+        );
+        assert_eq!(swizzle!(bv; 4), NanoBV::<u16>::new(1, 1));
+        let word = NanoBV::<u32>::new(0x1234_5678, 32);
+        assert_eq!(
+            swizzle!(word; 8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7),
+            NanoBV::<u32>::new(0x7856, 16)
+        );
+    }
+}
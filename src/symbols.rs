@@ -0,0 +1,185 @@
+//! Fixed-width symbol packing.
+//!
+//! Symbols of `width` bits are packed with symbol `i` at bits
+//! `i * width .. (i + 1) * width`, so the first symbol is the least
+//! significant. The DNA helpers use the 2-bit alphabet of [`DNA_ALPHABET`].
+
+use crate::NanoBV;
+
+/// Nucleotides in the order of their 2-bit codes.
+pub const DNA_ALPHABET: [u8; 4] = *b"ACGT";
+
+/// Map an ASCII nucleotide to its 2-bit code, ignoring case.
+pub const fn dna_code(nucleotide: u8) -> Option<u8> {
+    match nucleotide {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
+    }
+}
+
+macro_rules! ImplNanoBVSymbols {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVSymbols!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Pack `symbols` of `width` bits each, returning `None` if a symbol
+            /// does not fit in `width` bits or the result does not fit in the
+            /// backing type.
+            pub const fn pack_symbols(symbols: &[u8], width: usize) -> Option<Self> {
+                ["Invalid width provided."][(width == 0 || width > 8) as usize];
+                let length = symbols.len() * width;
+                if length == 0 || length > Self::BIT_SIZE {
+                    return None;
+                }
+                let (mut data, mut i) = (0 as $type, 0);
+                while i < symbols.len() {
+                    if (symbols[i] as u16) >> width != 0 {
+                        return None;
+                    }
+                    data |= (symbols[i] as $type) << (i * width);
+                    i += 1;
+                }
+                Some(NanoBV::<$type>::new(data, length))
+            }
+
+            /// Unpack the symbols of `width` bits into `buf`, returning the
+            /// number of symbols written or `None` if `buf` is too short.
+            ///
+            /// Trailing bits that do not form a whole symbol are ignored.
+            pub fn unpack_symbols(&self, width: usize, buf: &mut [u8]) -> Option<usize> {
+                let count = self.symbol_count(width);
+                if buf.len() < count {
+                    return None;
+                }
+                for (i, symbol) in buf.iter_mut().take(count).enumerate() {
+                    *symbol = self.get_symbol(i, width);
+                }
+                Some(count)
+            }
+
+            /// Retrieve the number of whole symbols of `width` bits.
+            pub const fn symbol_count(&self, width: usize) -> usize {
+                ["Invalid width provided."][(width == 0 || width > 8) as usize];
+                match width {
+                1 => self.len(),
+                2 => self.len() >> 1,
+                4 => self.len() >> 2,
+                8 => self.len() >> 3,
+                _ => {
+                    let (mut count, mut remaining) = (0, self.len());
+                    while remaining >= width {
+                        remaining -= width;
+                        count += 1;
+                    }
+                    count
+                }
+                }
+            }
+
+            /// Retrieve the symbol of `width` bits at `index`.
+            pub const fn get_symbol(&self, index: usize, width: usize) -> u8 {
+                ["Invalid index provided."][(index >= self.symbol_count(width)) as usize];
+                ((self.data >> (index * width)) as u8) & ((1u16 << width) - 1) as u8
+            }
+
+            /// Replace the symbol of `width` bits at `index` with `symbol`.
+            pub const fn set_symbol(&self, index: usize, width: usize, symbol: u8) -> Self {
+                ["Invalid index provided."][(index >= self.symbol_count(width)) as usize];
+                ["Invalid symbol provided."][((symbol as u16) >> width != 0) as usize];
+                let mask = (((1u16 << width) - 1) as $type) << (index * width);
+                NanoBV::<$type>::new((self.data & !mask) | ((symbol as $type) << (index * width)), self.len())
+            }
+
+            /// Pack an ASCII nucleotide sequence with 2 bits per nucleotide,
+            /// returning `None` on an unknown nucleotide or if the sequence does
+            /// not fit in the backing type.
+            pub const fn pack_dna(sequence: &[u8]) -> Option<Self> {
+                let length = sequence.len() * 2;
+                if length == 0 || length > Self::BIT_SIZE {
+                    return None;
+                }
+                let (mut packed, mut i) = (NanoBV::<$type>::zeros(length), 0);
+                while i < sequence.len() {
+                    packed = match dna_code(sequence[i]) {
+                        Some(code) => packed.set_symbol(i, 2, code),
+                        None => return None,
+                    };
+                    i += 1;
+                }
+                Some(packed)
+            }
+
+            /// Unpack 2-bit codes into uppercase ASCII nucleotides in `buf`,
+            /// returning the number of nucleotides written or `None` if `buf` is
+            /// too short.
+            pub fn unpack_dna(&self, buf: &mut [u8]) -> Option<usize> {
+                let count = self.unpack_symbols(2, buf)?;
+                for nucleotide in buf.iter_mut().take(count) {
+                    *nucleotide = DNA_ALPHABET[*nucleotide as usize];
+                }
+                Some(count)
+            }
+        }
+    };
+}
+
+ImplNanoBVSymbols!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+
+    macro_rules! ImplNanoBVSymbolsTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVSymbolsTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_symbols_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let packed = NBV::pack_symbols(&[5, 7], 3).unwrap();
+                assert_eq!(packed, NBV::new(0b111_101, 6));
+                assert_eq!(packed.symbol_count(3), 2);
+                assert_eq!(NBV::new(0, 7).symbol_count(1), 7);
+                assert_eq!(NBV::new(0, 7).symbol_count(2), 3);
+                assert_eq!(NBV::new(0, 7).symbol_count(4), 1);
+                assert_eq!(NBV::new(0, 7).symbol_count(8), 0);
+                assert_eq!(NBV::new(0, 7).symbol_count(5), 1);
+                assert_eq!(packed.get_symbol(1, 3), 7);
+                assert_eq!(packed.set_symbol(1, 3, 2), NBV::new(0b010_101, 6));
+                let mut buf = [0; 4];
+                assert_eq!(packed.unpack_symbols(3, &mut buf), Some(2));
+                assert_eq!(buf[..2], [5, 7]);
+                assert_eq!(packed.unpack_symbols(3, &mut buf[..1]), None);
+                assert_eq!(NBV::pack_symbols(&[4], 2), None);
+                assert_eq!(NBV::pack_symbols(&[], 2), None);
+                let symbols = [1; 33];
+                assert_eq!(NBV::pack_symbols(&symbols[..NBV::BIT_SIZE / 2], 2), Some(NBV::new(<$type>::MAX / 3, NBV::BIT_SIZE)));
+                assert_eq!(NBV::pack_symbols(&symbols[..NBV::BIT_SIZE / 2 + 1], 2), None);
+            }
+
+            #[test]
+            fn [<test_nanobv_dna_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let packed = NBV::pack_dna(b"GaTc").unwrap();
+                assert_eq!(packed, NBV::new(0b01_11_00_10, 8));
+                let mut buf = [0; 4];
+                assert_eq!(packed.unpack_dna(&mut buf), Some(4));
+                assert_eq!(&buf, b"GATC");
+                assert_eq!(NBV::pack_dna(b"GATN"), None);
+                assert_eq!(NBV::pack_dna(b""), None);
+            }
+        }
+        };
+    }
+
+    ImplNanoBVSymbolsTest!(for u8, u16, u32, u64);
+}
@@ -0,0 +1,208 @@
+//! UART frame assembly and parsing.
+//!
+//! A frame holds its bits in transmission order from the least significant
+//! bit: the start bit (0), the data bits least significant first, the
+//! optional parity bit, then the stop bits (1). Shifting a frame out from its
+//! least significant bit therefore drives a software-serial line directly:
+//!
+//! ```
+//! use nanobv::{
+//!     uart::{Parity, UartFormat},
+//!     Error, NanoBV,
+//! };
+//!
+//! let format = UartFormat::new(7, Parity::Even, 1);
+//! let frame = format.encode(NanoBV::<u16>::new(0x41, 7));
+//! assert_eq!(frame, NanoBV::<u16>::new(0b10_1000_0010, 10));
+//! assert_eq!(format.decode(frame), Ok(NanoBV::<u16>::new(0x41, 7)));
+//! assert_eq!(format.decode(frame.clear_bit(9)), Err(Error::Framing));
+//! assert_eq!(format.decode(frame.set_bit(2)), Err(Error::Parity));
+//! ```
+
+use crate::{Error, NanoBV};
+
+/// Parity bit following the data bits.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum Parity {
+    /// No parity bit.
+    None,
+    /// Parity bit making the number of set data and parity bits even.
+    Even,
+    /// Parity bit making the number of set data and parity bits odd.
+    Odd,
+    /// Parity bit always set.
+    Mark,
+    /// Parity bit always cleared.
+    Space,
+}
+
+/// Character format of a UART, such as 8N1.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub struct UartFormat {
+    data_bits: usize,
+    parity: Parity,
+    stop_bits: usize,
+}
+
+impl UartFormat {
+    /// 8 data bits, no parity and 1 stop bit.
+    pub const FORMAT_8N1: UartFormat = UartFormat::new(8, Parity::None, 1);
+
+    /// Create a [`UartFormat`] with 5 to 9 data bits and 1 or 2 stop bits.
+    pub const fn new(
+        data_bits: usize,
+        parity: Parity,
+        stop_bits: usize,
+    ) -> Self {
+        assert!(
+            data_bits >= 5 && data_bits <= 9,
+            "Invalid data bits provided."
+        );
+        assert!(
+            stop_bits == 1 || stop_bits == 2,
+            "Invalid stop bits provided."
+        );
+        UartFormat { data_bits, parity, stop_bits }
+    }
+
+    /// Retrieve the number of data bits.
+    pub const fn data_bits(&self) -> usize {
+        self.data_bits
+    }
+
+    /// Retrieve the parity.
+    pub const fn parity(&self) -> Parity {
+        self.parity
+    }
+
+    /// Retrieve the number of stop bits.
+    pub const fn stop_bits(&self) -> usize {
+        self.stop_bits
+    }
+
+    /// Retrieve the number of bits of a frame, including the start bit.
+    pub const fn frame_len(&self) -> usize {
+        1 + self.data_bits + self.parity_bits() + self.stop_bits
+    }
+
+    const fn parity_bits(&self) -> usize {
+        !matches!(self.parity, Parity::None) as usize
+    }
+
+    /// Compute the parity bit of `data`.
+    const fn parity_bit(&self, data: u16) -> u16 {
+        match self.parity {
+            Parity::None | Parity::Space => 0,
+            Parity::Even => (data.count_ones() % 2) as u16,
+            Parity::Odd => 1 - (data.count_ones() % 2) as u16,
+            Parity::Mark => 1,
+        }
+    }
+
+    /// Assemble the frame carrying `data`, which must be
+    /// [`data_bits`](Self::data_bits) long.
+    pub const fn encode(&self, data: NanoBV<u16>) -> NanoBV<u16> {
+        assert!(data.len() == self.data_bits, "Invalid length provided.");
+        let stop = (1 << self.stop_bits) - 1;
+        let parity = self.parity_bit(data.value()) << self.data_bits;
+        let frame = ((stop << (self.data_bits + self.parity_bits()))
+            | parity
+            | data.value())
+            << 1;
+        NanoBV::<u16>::new(frame, self.frame_len())
+    }
+
+    /// Parse a frame, returning its data bits.
+    ///
+    /// Returns [`Error::InvalidLength`] if the frame is not
+    /// [`frame_len`](Self::frame_len) long, [`Error::Framing`] if the start bit is set or
+    /// a stop bit is cleared, and [`Error::Parity`] if the parity bit does
+    /// not match the data.
+    pub const fn decode(
+        &self,
+        frame: NanoBV<u16>,
+    ) -> Result<NanoBV<u16>, Error> {
+        if frame.len() != self.frame_len() {
+            return Err(Error::InvalidLength);
+        }
+        let value = frame.value();
+        let stop = value >> (1 + self.data_bits + self.parity_bits());
+        if value & 1 != 0 || stop != (1 << self.stop_bits) - 1 {
+            return Err(Error::Framing);
+        }
+        let data = NanoBV::<u16>::new(value >> 1, self.data_bits);
+        let parity = (value >> (1 + self.data_bits)) & 1;
+        match self.parity_bits() == 0 || parity == self.parity_bit(data.value())
+        {
+            true => Ok(data),
+            false => Err(Error::Parity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uart_encode() {
+        let format = UartFormat::FORMAT_8N1;
+        assert_eq!(format.frame_len(), 10);
+        assert_eq!(
+            format.encode(NanoBV::<u16>::new(0x55, 8)),
+            NanoBV::<u16>::new(0b10_1010_1010, 10)
+        );
+        let format = UartFormat::new(5, Parity::Odd, 2);
+        assert_eq!(format.frame_len(), 9);
+        assert_eq!(
+            format.encode(NanoBV::<u16>::new(0b1_0011, 5)),
+            NanoBV::<u16>::new(0b1_1010_0110, 9)
+        );
+        let format = UartFormat::new(9, Parity::Mark, 1);
+        assert_eq!(
+            format.encode(NanoBV::<u16>::zeros(9)),
+            NanoBV::<u16>::new(0b1100_0000_0000, 12)
+        );
+        let format = UartFormat::new(9, Parity::Space, 1);
+        assert_eq!(
+            format.encode(NanoBV::<u16>::ones(9)),
+            NanoBV::<u16>::new(0b1011_1111_1110, 12)
+        );
+    }
+
+    #[test]
+    fn test_uart_decode() {
+        for parity in [
+            Parity::None,
+            Parity::Even,
+            Parity::Odd,
+            Parity::Mark,
+            Parity::Space,
+        ] {
+            for stop_bits in 1..=2 {
+                let format = UartFormat::new(8, parity, stop_bits);
+                for value in 0..=u8::MAX {
+                    let data = NanoBV::<u16>::new(value as u16, 8);
+                    assert_eq!(format.decode(format.encode(data)), Ok(data));
+                }
+            }
+        }
+        let format = UartFormat::new(8, Parity::Even, 2);
+        let frame = format.encode(NanoBV::<u16>::new(0x80, 8));
+        assert_eq!(format.decode(frame.set_bit(0)), Err(Error::Framing));
+        assert_eq!(format.decode(frame.clear_bit(10)), Err(Error::Framing));
+        assert_eq!(format.decode(frame.clear_bit(11)), Err(Error::Framing));
+        assert_eq!(format.decode(frame.clear_bit(9)), Err(Error::Parity));
+        assert_eq!(format.decode(frame.clear_bit(8)), Err(Error::Parity));
+        assert_eq!(
+            format.decode(NanoBV::<u16>::new(frame.value(), 11)),
+            Err(Error::InvalidLength)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_uart_invalid_format() {
+        UartFormat::new(10, Parity::None, 1);
+    }
+}
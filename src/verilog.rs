@@ -0,0 +1,207 @@
+//! Verilog sized literals such as `12'habc` and `8'b1010_1100`.
+
+use crate::{
+    internals::{digit_value, parse_decimal, write_decimal, write_digits},
+    Error, NanoBV,
+};
+
+/// Radix of a Verilog sized literal.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum Radix {
+    /// `'b`
+    Binary,
+    /// `'o`
+    Octal,
+    /// `'d`
+    Decimal,
+    /// `'h`
+    Hex,
+}
+
+impl Radix {
+    const fn from_specifier(specifier: u8) -> Option<Self> {
+        match specifier {
+            b'b' | b'B' => Some(Radix::Binary),
+            b'o' | b'O' => Some(Radix::Octal),
+            b'd' | b'D' => Some(Radix::Decimal),
+            b'h' | b'H' => Some(Radix::Hex),
+            _ => None,
+        }
+    }
+
+    const fn specifier(&self) -> u8 {
+        match self {
+            Radix::Binary => b'b',
+            Radix::Octal => b'o',
+            Radix::Decimal => b'd',
+            Radix::Hex => b'h',
+        }
+    }
+
+    const fn base(&self) -> u64 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hex => 16,
+        }
+    }
+}
+
+macro_rules! ImplNanoBVVerilog {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVVerilog!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Write the current NanoBV as a Verilog sized literal into `buf`,
+            /// returning the number of bytes written.
+            ///
+            /// Binary, octal and hexadecimal digits are zero-padded to the length.
+            pub fn write_verilog(&self, radix: Radix, buf: &mut [u8]) -> Result<usize, Error> {
+                let mut written = write_decimal(self.len() as u64, buf)?;
+                match buf.get_mut(written..written + 2) {
+                Some([quote, specifier]) => {
+                    *quote = b'\'';
+                    *specifier = radix.specifier();
+                }
+                _ => return Err(Error::BufferTooSmall),
+                }
+                written += 2;
+                let digits = &mut buf[written..];
+                written += match radix {
+                Radix::Binary => write_digits(self.data as u64, self.len(), 1, digits)?,
+                Radix::Octal => write_digits(self.data as u64, self.len().div_ceil(3), 3, digits)?,
+                Radix::Decimal => write_decimal(self.data as u64, digits)?,
+                Radix::Hex => write_digits(self.data as u64, self.len().div_ceil(4), 4, digits)?,
+                };
+                Ok(written)
+            }
+
+            /// Parse a Verilog sized literal, keeping its width as the length.
+            ///
+            /// Underscores are ignored and the signed specifier (`8'sh80`) is
+            /// accepted. Values that do not fit the width and `x`/`z` digits are
+            /// rejected.
+            pub const fn from_verilog(literal: &str) -> Result<Self, Error> {
+                let bytes = literal.as_bytes();
+                let (length, mut i) = match parse_decimal(bytes, 0) {
+                Ok(parsed) => parsed,
+                Err(error) => return Err(error),
+                };
+                if length < 1 || length > Self::BIT_SIZE as u64 {
+                    return Err(Error::InvalidLength);
+                }
+                if i >= bytes.len() || bytes[i] != b'\'' {
+                    return Err(Error::InvalidFormat);
+                }
+                i += 1;
+                if i < bytes.len() && (bytes[i] == b's' || bytes[i] == b'S') {
+                    i += 1;
+                }
+                if i >= bytes.len() {
+                    return Err(Error::InvalidFormat);
+                }
+                let radix = match Radix::from_specifier(bytes[i]) {
+                Some(radix) => radix,
+                None => return Err(Error::InvalidFormat),
+                };
+                i += 1;
+                let (mut value, mut digits) = (0u64, 0);
+                while i < bytes.len() {
+                    if bytes[i] != b'_' {
+                        let digit = digit_value(bytes[i]) as u64;
+                        if digit >= radix.base() {
+                            return Err(Error::InvalidDigit);
+                        }
+                        value = match value.checked_mul(radix.base()) {
+                        Some(value) => match value.checked_add(digit) {
+                            Some(value) => value,
+                            None => return Err(Error::Overflow),
+                        },
+                        None => return Err(Error::Overflow),
+                        };
+                        digits += 1;
+                    }
+                    i += 1;
+                }
+                if digits == 0 {
+                    return Err(Error::InvalidFormat);
+                }
+                if length < 64 && value >> length != 0 {
+                    return Err(Error::Overflow);
+                }
+                Ok(NanoBV::<$type>::new(value as $type, length as usize))
+            }
+        }
+    };
+}
+
+ImplNanoBVVerilog!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVVerilogTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVVerilogTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_write_verilog_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut buf = [0u8; 72];
+                let bv = NBV::new(0b1010_1100, 8);
+                let written = bv.write_verilog(Radix::Binary, &mut buf).unwrap();
+                assert_eq!(&buf[..written], b"8'b10101100");
+                let written = bv.write_verilog(Radix::Hex, &mut buf).unwrap();
+                assert_eq!(&buf[..written], b"8'hac");
+                let written = bv.write_verilog(Radix::Octal, &mut buf).unwrap();
+                assert_eq!(&buf[..written], b"8'o254");
+                let written = bv.write_verilog(Radix::Decimal, &mut buf).unwrap();
+                assert_eq!(&buf[..written], b"8'd172");
+                let written = NBV::new(0x5, 7).write_verilog(Radix::Hex, &mut buf).unwrap();
+                assert_eq!(&buf[..written], b"7'h05");
+                assert_eq!(bv.write_verilog(Radix::Binary, &mut buf[..10]), Err(Error::BufferTooSmall));
+            }
+
+            #[test]
+            fn [<test_nanobv_from_verilog_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::from_verilog("8'b1010_1100"), Ok(NBV::new(0b1010_1100, 8)));
+                assert_eq!(NBV::from_verilog("7'hA"), Ok(NBV::new(0xA, 7)));
+                assert_eq!(NBV::from_verilog("8'sh80"), Ok(NBV::new(0x80, 8)));
+                assert_eq!(NBV::from_verilog("6'o77"), Ok(NBV::new(0o77, 6)));
+                assert_eq!(NBV::from_verilog("5'D31"), Ok(NBV::new(31, 5)));
+                assert_eq!(NBV::from_verilog("5'd32"), Err(Error::Overflow));
+                assert_eq!(NBV::from_verilog("4'b10x1"), Err(Error::InvalidDigit));
+                assert_eq!(NBV::from_verilog("0'b0"), Err(Error::InvalidLength));
+                assert_eq!(NBV::from_verilog("'hff"), Err(Error::InvalidFormat));
+                assert_eq!(NBV::from_verilog("8'q12"), Err(Error::InvalidFormat));
+                assert_eq!(NBV::from_verilog("8'h_"), Err(Error::InvalidFormat));
+            }
+
+            #[test]
+            fn [<test_nanobv_verilog_roundtrip_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let mut buf = [0u8; 72];
+                for &radix in [Radix::Binary, Radix::Octal, Radix::Decimal, Radix::Hex].iter() {
+                    let written = bv.write_verilog(radix, &mut buf).unwrap();
+                    let literal = core::str::from_utf8(&buf[..written]).unwrap();
+                    assert_eq!(NBV::from_verilog(literal), Ok(bv));
+                }
+            }
+        }
+        };
+    }
+
+    ImplNanoBVVerilogTest!(for u8, u16, u32, u64);
+}
@@ -0,0 +1,233 @@
+//! VHDL `std_logic_vector` string conversions.
+//!
+//! Bit strings are written most significant bit first, as in `"10110010"`,
+//! and hexadecimal strings use the VHDL-2008 sized form, as in `8X"B2"`.
+
+use crate::{
+    internals::{digit_value, parse_decimal, write_decimal, write_digits},
+    Error, NanoBV,
+};
+
+/// Handling of `std_logic` metavalues (`U`, `X`, `Z`, `W` and `-`) when
+/// parsing. The weak levels `L` and `H` are always read as `0` and `1`.
+#[derive(PartialEq, Debug, Eq, Copy, Clone)]
+pub enum MetaValue {
+    /// Reject metavalues with [`Error::InvalidDigit`].
+    Reject,
+    /// Read metavalues as `0`.
+    Zero,
+    /// Read metavalues as `1`.
+    One,
+}
+
+impl MetaValue {
+    const fn resolve(&self, byte: u8, radix_bits: usize) -> Option<u8> {
+        let digit = match byte {
+            b'L' | b'l' if radix_bits == 1 => 0,
+            b'H' | b'h' if radix_bits == 1 => 1,
+            b'U' | b'u' | b'X' | b'x' | b'Z' | b'z' | b'W' | b'w' | b'-' => {
+                match self {
+                    MetaValue::Reject => return None,
+                    MetaValue::Zero => 0,
+                    MetaValue::One => (1 << radix_bits) - 1,
+                }
+            }
+            _ => digit_value(byte),
+        };
+        match (digit as usize) < 1 << radix_bits {
+            true => Some(digit),
+            false => None,
+        }
+    }
+}
+
+/// Strip an optional `[width]X"..."` (or `"..."`) wrapper from `bytes`,
+/// returning the bounds of its digits.
+const fn unquote(bytes: &[u8], prefix: u8) -> Result<(usize, usize), Error> {
+    let mut start = 0;
+    while start < bytes.len() && bytes[start].is_ascii_digit() {
+        start += 1;
+    }
+    if start < bytes.len() && (bytes[start] | 0x20) == prefix {
+        start += 1;
+    }
+    match (start < bytes.len() && bytes[start] == b'"', bytes.len()) {
+        (true, end) if end >= start + 2 && bytes[end - 1] == b'"' => {
+            Ok((start + 1, end - 1))
+        }
+        (false, end) => Ok((0, end)),
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+macro_rules! ImplNanoBVVhdl {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVVhdl!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            const fn vhdl_parse(
+                bytes: &[u8],
+                start: usize,
+                end: usize,
+                radix_bits: usize,
+                length: usize,
+                meta: MetaValue,
+            ) -> Result<Self, Error> {
+                if length < 1 || length > Self::BIT_SIZE {
+                    return Err(Error::InvalidLength);
+                }
+                if start >= end {
+                    return Err(Error::InvalidFormat);
+                }
+                let (mut value, mut i) = (0u64, start);
+                while i < end {
+                    let digit = match meta.resolve(bytes[i], radix_bits) {
+                    Some(digit) => digit,
+                    None => return Err(Error::InvalidDigit),
+                    };
+                    if value >> (64 - radix_bits) != 0 {
+                        return Err(Error::Overflow);
+                    }
+                    value = (value << radix_bits) | digit as u64;
+                    i += 1;
+                }
+                if length < 64 && value >> length != 0 {
+                    return Err(Error::Overflow);
+                }
+                Ok(NanoBV::<$type>::new(value as $type, length))
+            }
+
+            /// Write the current NanoBV as a bit string such as `10110010`,
+            /// returning the number of bytes written.
+            pub fn write_vhdl_bits(&self, buf: &mut [u8]) -> Result<usize, Error> {
+                write_digits(self.data as u64, self.len(), 1, buf)
+            }
+
+            /// Write the current NanoBV as a sized hexadecimal string such as
+            /// `8X"B2"`, returning the number of bytes written.
+            pub fn write_vhdl_hex(&self, buf: &mut [u8]) -> Result<usize, Error> {
+                let mut written = write_decimal(self.len() as u64, buf)?;
+                match buf.get_mut(written..written + 2) {
+                Some([prefix, quote]) => {
+                    *prefix = b'X';
+                    *quote = b'"';
+                }
+                _ => return Err(Error::BufferTooSmall),
+                }
+                written += 2;
+                let digits = &mut buf[written..];
+                let count = write_digits(self.data as u64, self.len().div_ceil(4), 4, digits)?;
+                digits[..count].make_ascii_uppercase();
+                written += count;
+                *buf.get_mut(written).ok_or(Error::BufferTooSmall)? = b'"';
+                Ok(written + 1)
+            }
+
+            /// Parse a bit string such as `10110010` or `"10110010"`, taking
+            /// the number of digits as the length.
+            pub const fn from_vhdl_bits(literal: &str, meta: MetaValue) -> Result<Self, Error> {
+                let bytes = literal.as_bytes();
+                let (start, end) = match bytes {
+                [b'"', .., b'"'] => (1, bytes.len() - 1),
+                _ => (0, bytes.len()),
+                };
+                Self::vhdl_parse(bytes, start, end, 1, end.saturating_sub(start), meta)
+            }
+
+            /// Parse a hexadecimal string with an explicit `width`, such as
+            /// `B2`, `X"B2"` or `8X"B2"`.
+            ///
+            /// Metavalue digits expand to four bits, as in VHDL.
+            pub const fn from_vhdl_hex(literal: &str, width: usize, meta: MetaValue) -> Result<Self, Error> {
+                let bytes = literal.as_bytes();
+                let (start, end) = match unquote(bytes, b'x') {
+                Ok(bounds) => bounds,
+                Err(error) => return Err(error),
+                };
+                if start > 0 && bytes[0].is_ascii_digit() {
+                    match parse_decimal(bytes, 0) {
+                    Ok((declared, _)) if declared == width as u64 => (),
+                    _ => return Err(Error::InvalidLength),
+                    }
+                }
+                Self::vhdl_parse(bytes, start, end, 4, width, meta)
+            }
+        }
+    };
+}
+
+ImplNanoBVVhdl!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVVhdlTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVVhdlTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_write_vhdl_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut buf = [0u8; 72];
+                let bv = NBV::new(0b1011_0010, 8);
+                let written = bv.write_vhdl_bits(&mut buf).unwrap();
+                assert_eq!(&buf[..written], b"10110010");
+                let written = bv.write_vhdl_hex(&mut buf).unwrap();
+                assert_eq!(&buf[..written], b"8X\"B2\"");
+                let written = NBV::new(0x1F, 5).write_vhdl_hex(&mut buf).unwrap();
+                assert_eq!(&buf[..written], b"5X\"1F\"");
+                assert_eq!(bv.write_vhdl_hex(&mut buf[..5]), Err(Error::BufferTooSmall));
+            }
+
+            #[test]
+            fn [<test_nanobv_from_vhdl_bits_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::from_vhdl_bits("10110010", MetaValue::Reject), Ok(NBV::new(0b1011_0010, 8)));
+                assert_eq!(NBV::from_vhdl_bits("\"0HL1\"", MetaValue::Reject), Ok(NBV::new(0b0101, 4)));
+                assert_eq!(NBV::from_vhdl_bits("1X0Z", MetaValue::Reject), Err(Error::InvalidDigit));
+                assert_eq!(NBV::from_vhdl_bits("1X0Z", MetaValue::Zero), Ok(NBV::new(0b1000, 4)));
+                assert_eq!(NBV::from_vhdl_bits("1X0Z", MetaValue::One), Ok(NBV::new(0b1101, 4)));
+                assert_eq!(NBV::from_vhdl_bits("102", MetaValue::One), Err(Error::InvalidDigit));
+                assert_eq!(NBV::from_vhdl_bits("\"\"", MetaValue::Reject), Err(Error::InvalidLength));
+            }
+
+            #[test]
+            fn [<test_nanobv_from_vhdl_hex_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                assert_eq!(NBV::from_vhdl_hex("B2", 8, MetaValue::Reject), Ok(NBV::new(0xB2, 8)));
+                assert_eq!(NBV::from_vhdl_hex("x\"b2\"", 8, MetaValue::Reject), Ok(NBV::new(0xB2, 8)));
+                assert_eq!(NBV::from_vhdl_hex("5X\"1F\"", 5, MetaValue::Reject), Ok(NBV::new(0x1F, 5)));
+                assert_eq!(NBV::from_vhdl_hex("6X\"1F\"", 5, MetaValue::Reject), Err(Error::InvalidLength));
+                assert_eq!(NBV::from_vhdl_hex("3F", 5, MetaValue::Reject), Err(Error::Overflow));
+                assert_eq!(NBV::from_vhdl_hex("1X", 8, MetaValue::Reject), Err(Error::InvalidDigit));
+                assert_eq!(NBV::from_vhdl_hex("1X", 8, MetaValue::One), Ok(NBV::new(0x1F, 8)));
+                assert_eq!(NBV::from_vhdl_hex("X\"B2", 8, MetaValue::Reject), Err(Error::InvalidFormat));
+            }
+
+            #[test]
+            fn [<test_nanobv_vhdl_roundtrip_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                let bv = NBV::new(rng.generate(), NBV::BIT_SIZE);
+                let mut buf = [0u8; 72];
+                let written = bv.write_vhdl_bits(&mut buf).unwrap();
+                let literal = core::str::from_utf8(&buf[..written]).unwrap();
+                assert_eq!(NBV::from_vhdl_bits(literal, MetaValue::Reject), Ok(bv));
+                let written = bv.write_vhdl_hex(&mut buf).unwrap();
+                let literal = core::str::from_utf8(&buf[..written]).unwrap();
+                assert_eq!(NBV::from_vhdl_hex(literal, NBV::BIT_SIZE, MetaValue::Reject), Ok(bv));
+            }
+        }
+        };
+    }
+
+    ImplNanoBVVhdlTest!(for u8, u16, u32, u64);
+}
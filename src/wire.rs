@@ -0,0 +1,110 @@
+//! Self-describing binary encoding.
+//!
+//! A NanoBV of length `n` is encoded in `1 + ceil(n / 8)` bytes: the length
+//! as a single byte, followed by the value in little-endian byte order.
+//! Unused high bits of the last byte are zero.
+//!
+//! ```
+//! use nanobv::NanoBV;
+//!
+//! let mut buf = [0; 3];
+//! let bv = NanoBV::<u16>::new(0x2BC, 10);
+//! assert_eq!(bv.encode_into(&mut buf), Ok(3));
+//! assert_eq!(buf, [10, 0xBC, 0x02]);
+//! assert_eq!(NanoBV::<u16>::decode_from(&buf), Ok((bv, 3)));
+//! ```
+
+use crate::{Error, NanoBV};
+
+macro_rules! ImplNanoBVWire {
+    (for $($type:tt),+) => {
+        $(ImplNanoBVWire!($type);)*
+    };
+
+    ($type:ident) => {
+        impl NanoBV<$type> {
+            /// Retrieve the number of bytes written by [`NanoBV::encode_into`].
+            pub const fn encoded_len(&self) -> usize {
+                1 + self.len().div_ceil(8)
+            }
+
+            /// Encode the length and value into `buf`, returning the number of
+            /// bytes written.
+            pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, Error> {
+                let buf = buf.get_mut(..self.encoded_len()).ok_or(Error::BufferTooSmall)?;
+                buf[0] = self.len() as u8;
+                buf[1..].copy_from_slice(&self.data.to_le_bytes()[..self.len().div_ceil(8)]);
+                Ok(buf.len())
+            }
+
+            /// Decode a NanoBV from the start of `bytes`, returning it with the
+            /// number of bytes read.
+            pub const fn decode_from(bytes: &[u8]) -> Result<(Self, usize), Error> {
+                let length = match bytes.first() {
+                Some(&length) => length as usize,
+                None => return Err(Error::InvalidFormat),
+                };
+                if length < 1 || length > Self::BIT_SIZE {
+                    return Err(Error::InvalidLength);
+                }
+                let count = length.div_ceil(8);
+                if bytes.len() < 1 + count {
+                    return Err(Error::InvalidFormat);
+                }
+                let (mut value, mut i) = (0 as $type, 0);
+                while i < count {
+                    value |= (bytes[1 + i] as $type) << (8 * i);
+                    i += 1;
+                }
+                if length < Self::BIT_SIZE && value >> length != 0 {
+                    return Err(Error::Overflow);
+                }
+                Ok((NanoBV::<$type>::new(value, length), 1 + count))
+            }
+        }
+    };
+}
+
+ImplNanoBVWire!(for u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::paste;
+    use picorand::{PicoRandGenerate, WyRand, RNG};
+
+    macro_rules! ImplNanoBVWireTest {
+        (for $($type:tt),+) => {
+            $(ImplNanoBVWireTest!($type);)*
+        };
+
+        ($type:ident) => {
+        paste! {
+            #[test]
+            fn [<test_nanobv_wire_ $type>]() {
+                type NBV = NanoBV::<$type>;
+                let mut buf = [0xFF; 9];
+                let bv = NBV::new(0b101, 3);
+                assert_eq!(bv.encode_into(&mut buf), Ok(2));
+                assert_eq!(buf[..2], [3, 0b101]);
+                assert_eq!(NBV::decode_from(&buf), Ok((bv, 2)));
+                assert_eq!(bv.encode_into(&mut buf[..1]), Err(Error::BufferTooSmall));
+                assert_eq!(NBV::decode_from(&[]), Err(Error::InvalidFormat));
+                assert_eq!(NBV::decode_from(&[0, 0]), Err(Error::InvalidLength));
+                assert_eq!(NBV::decode_from(&[NBV::BIT_SIZE as u8 + 1; 10]), Err(Error::InvalidLength));
+                assert_eq!(NBV::decode_from(&[3, 0b1000]), Err(Error::Overflow));
+                assert_eq!(NBV::decode_from(&[NBV::BIT_SIZE as u8]), Err(Error::InvalidFormat));
+                let mut rng = RNG::<WyRand, $type>::new($type::MAX as _);
+                for _ in 0..100 {
+                    let bv = NBV::new(rng.generate(), rng.generate_range(1, NBV::BIT_SIZE) as usize);
+                    let written = bv.encode_into(&mut buf).unwrap();
+                    assert_eq!(written, bv.encoded_len());
+                    assert_eq!(NBV::decode_from(&buf[..written]), Ok((bv, written)));
+                }
+            }
+        }
+        };
+    }
+
+    ImplNanoBVWireTest!(for u8, u16, u32, u64);
+}